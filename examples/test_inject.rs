@@ -1,15 +1,18 @@
+use scribe::config::schema::InjectionConfig;
 use scribe::input::inject::TextInjector;
 use std::io::{self, Write};
 use std::thread;
 use std::time::Duration;
 
-/// Example demonstrating text injection via dotool
+/// Example demonstrating text injection
 ///
 /// This example shows how to use the `TextInjector` to type text into the active window.
-/// Run with: `cargo run --example test_inject`
+/// Run with: `cargo run --example test_inject [method]`, where method defaults to `auto`.
+/// `SCRIBE_LAYOUT` sets the XKB layout of the uinput method (default `us`).
 ///
 /// Requirements:
-/// - dotool must be installed and in PATH
+/// - Write access to /dev/uinput, or the tool for the chosen method (dotool, ydotool,
+///   wtype, xdotool or a clipboard tool) installed and in PATH
 /// - You must have focus on a text editor or other text input
 ///
 /// Usage:
@@ -20,10 +23,18 @@ fn main() -> anyhow::Result<()> {
     println!("Text Injection Example");
     println!("======================\n");
 
-    // Check if dotool is available
-    match TextInjector::new(2) {
+    let config = InjectionConfig {
+        method: std::env::args()
+            .nth(1)
+            .unwrap_or_else(|| "auto".to_string()),
+        delay_ms: 2,
+        layout: std::env::var("SCRIBE_LAYOUT").unwrap_or_else(|_| "us".to_string()),
+    };
+
+    // Check if the injection tool is available
+    match TextInjector::from_config(&config) {
         Ok(mut injector) => {
-            println!("✓ dotool found\n");
+            println!("✓ Using {}\n", injector.method());
 
             println!("This example will type text into your active window.");
             println!("\nInstructions:");
@@ -66,9 +77,9 @@ fn main() -> anyhow::Result<()> {
         Err(e) => {
             eprintln!("✗ Error: {e}");
             eprintln!("\nTo fix this:");
-            eprintln!("  1. Install dotool: cargo install dotool");
-            eprintln!("  2. Or on Arch: paru -S dotool");
-            eprintln!("  3. Ensure dotool is in your PATH");
+            eprintln!("  1. Get write access to /dev/uinput, or install ydotool, wtype or xdotool");
+            eprintln!("  2. Ensure the tool is in your PATH");
+            eprintln!("  3. Or pass another method: cargo run --example test_inject clipboard");
             std::process::exit(1);
         }
     }
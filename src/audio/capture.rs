@@ -1,13 +1,22 @@
+use crate::audio::resample::Resampler;
 use crate::error::{Result, ScribeError};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex};
+use cpal::{FromSample, Sample, SampleFormat, SizedSample};
 use tokio::sync::mpsc;
 
 /// Audio capture configuration and control
+///
+/// Captured audio is always delivered as mono i16 at the requested sample rate.
+/// Devices that only offer multi-channel or non-i16 streams are downmixed and
+/// converted in the capture callback. If the device cannot record at the requested
+/// rate, the closest supported rate is used and the audio is resampled before
+/// frames are sent.
 pub struct AudioCapture {
     device: cpal::Device,
     config: cpal::StreamConfig,
+    sample_format: SampleFormat,
     sample_rate: u32,
+    device_sample_rate: u32,
 }
 
 /// Handle to a running audio stream
@@ -28,7 +37,7 @@ impl AudioCapture {
         let device = if let Some(name) = device_name {
             host.input_devices()
                 .map_err(|e| ScribeError::Audio(format!("Failed to enumerate devices: {e}")))?
-                .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+                .find(|d| d.name().is_ok_and(|n| n == name))
                 .ok_or_else(|| ScribeError::Audio(format!("Device '{name}' not found")))?
         } else {
             host.default_input_device()
@@ -40,40 +49,61 @@ impl AudioCapture {
             .supported_input_configs()
             .map_err(|e| ScribeError::Audio(format!("Failed to get supported configs: {e}")))?;
 
+        // Prefer the closest rate, then mono over multi-channel, then native i16
         let mut best_config = None;
-        let mut best_diff = u32::MAX;
+        let mut best_score = (u32::MAX, true, true);
 
         for supported in supported_configs {
-            if supported.channels() == 1 && supported.sample_format() == cpal::SampleFormat::I16 {
-                for rate in [sample_rate, 16000, 48000, 44100] {
-                    if supported.min_sample_rate().0 <= rate
-                        && supported.max_sample_rate().0 >= rate
-                    {
-                        let diff = rate.abs_diff(sample_rate);
-                        if diff < best_diff {
-                            best_diff = diff;
-                            best_config = Some((supported, rate));
-                        }
-                        break;
-                    }
-                }
+            if !is_supported_format(supported.sample_format()) {
+                continue;
+            }
+
+            let rate =
+                sample_rate.clamp(supported.min_sample_rate().0, supported.max_sample_rate().0);
+            let score = (
+                rate.abs_diff(sample_rate),
+                supported.channels() != 1,
+                supported.sample_format() != SampleFormat::I16,
+            );
+            if score < best_score {
+                best_score = score;
+                best_config = Some((supported, rate));
             }
         }
 
-        let (_supported, actual_rate) = best_config.ok_or_else(|| {
-            ScribeError::Audio("No supported config found (need mono i16 at 16kHz)".to_string())
+        let (supported, device_rate) = best_config.ok_or_else(|| {
+            ScribeError::Audio("No supported config found (need i16, u16, i32 or f32)".to_string())
         })?;
 
+        if device_rate != sample_rate {
+            tracing::info!(
+                device_rate,
+                sample_rate,
+                "Device does not support requested sample rate, resampling"
+            );
+        }
+
+        let sample_format = supported.sample_format();
+        if supported.channels() != 1 || sample_format != SampleFormat::I16 {
+            tracing::info!(
+                channels = supported.channels(),
+                format = %sample_format,
+                "Device does not support mono i16, converting"
+            );
+        }
+
         let config = cpal::StreamConfig {
-            channels: 1,
-            sample_rate: cpal::SampleRate(actual_rate),
+            channels: supported.channels(),
+            sample_rate: cpal::SampleRate(device_rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
         Ok(Self {
             device,
             config,
-            sample_rate: actual_rate,
+            sample_format,
+            sample_rate,
+            device_sample_rate: device_rate,
         })
     }
 
@@ -82,38 +112,19 @@ impl AudioCapture {
     /// Returns `AudioStream` handle with receiver for audio frames
     pub fn start_recording(self) -> Result<AudioStream> {
         let (tx, rx) = mpsc::channel(100);
-        let buffer = Arc::new(Mutex::new(Vec::new()));
-        let buffer_clone = Arc::clone(&buffer);
-
-        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let frame_size = (f64::from(self.sample_rate) * 0.03) as usize; // 30ms frames
 
-        let stream = self
-            .device
-            .build_input_stream(
-                &self.config,
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    let mut buf = buffer_clone.lock().unwrap();
-                    buf.extend_from_slice(data);
-
-                    // Send complete frames
-                    while buf.len() >= frame_size {
-                        let frame: Vec<i16> = buf.drain(..frame_size).collect();
-                        drop(buf);
-                        if tx.blocking_send(frame).is_err() {
-                            // Receiver dropped, stop buffering
-                            return;
-                        }
-                        buf = buffer_clone.lock().unwrap();
-                    }
-                    drop(buf);
-                },
-                move |err| {
-                    eprintln!("Audio stream error: {err}");
-                },
-                None,
-            )
-            .map_err(|e| ScribeError::Audio(format!("Failed to build input stream: {e}")))?;
+        let stream = match self.sample_format {
+            SampleFormat::I16 => self.build_stream::<i16>(tx),
+            SampleFormat::U16 => self.build_stream::<u16>(tx),
+            SampleFormat::I32 => self.build_stream::<i32>(tx),
+            SampleFormat::F32 => self.build_stream::<f32>(tx),
+            other => {
+                return Err(ScribeError::Audio(format!(
+                    "Unsupported sample format: {other}"
+                )))
+            }
+        }
+        .map_err(|e| ScribeError::Audio(format!("Failed to build input stream: {e}")))?;
 
         stream
             .play()
@@ -125,6 +136,47 @@ impl AudioCapture {
         })
     }
 
+    /// Build input stream for device sample type `T`, sending mono i16 frames to `tx`
+    fn build_stream<T>(
+        &self,
+        tx: mpsc::Sender<Vec<i16>>,
+    ) -> std::result::Result<cpal::Stream, cpal::BuildStreamError>
+    where
+        T: SizedSample,
+        i16: FromSample<T>,
+    {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let frame_size = (f64::from(self.sample_rate) * 0.03) as usize; // 30ms frames
+        let channels = usize::from(self.config.channels);
+        let mut resampler = Resampler::new(self.device_sample_rate, self.sample_rate);
+        let mut buffer = Vec::new();
+
+        self.device.build_input_stream(
+            &self.config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                let mono = downmix(data, channels);
+                if resampler.is_passthrough() {
+                    buffer.extend_from_slice(&mono);
+                } else {
+                    buffer.extend(resampler.process(&mono));
+                }
+
+                // Send complete frames
+                while buffer.len() >= frame_size {
+                    let frame: Vec<i16> = buffer.drain(..frame_size).collect();
+                    if tx.blocking_send(frame).is_err() {
+                        // Receiver dropped, stop buffering
+                        return;
+                    }
+                }
+            },
+            move |err| {
+                eprintln!("Audio stream error: {err}");
+            },
+            None,
+        )
+    }
+
     /// List all available input devices
     #[must_use]
     pub fn list_devices() -> Vec<String> {
@@ -135,11 +187,46 @@ impl AudioCapture {
             .unwrap_or_default()
     }
 
-    /// Get sample rate
+    /// Get sample rate of delivered audio frames
     #[must_use]
     pub const fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
+
+    /// Get sample rate the device is actually recording at
+    #[must_use]
+    pub const fn device_sample_rate(&self) -> u32 {
+        self.device_sample_rate
+    }
+}
+
+/// Whether captured samples of this format can be converted to i16
+const fn is_supported_format(format: SampleFormat) -> bool {
+    matches!(
+        format,
+        SampleFormat::I16 | SampleFormat::U16 | SampleFormat::I32 | SampleFormat::F32
+    )
+}
+
+/// Convert interleaved samples to mono i16 by averaging each frame's channels
+fn downmix<T>(data: &[T], channels: usize) -> Vec<i16>
+where
+    T: Sample,
+    i16: FromSample<T>,
+{
+    if channels <= 1 {
+        return data.iter().map(|&s| s.to_sample::<i16>()).collect();
+    }
+
+    data.chunks_exact(channels)
+        .map(|frame| {
+            let sum: i32 = frame.iter().map(|&s| i32::from(s.to_sample::<i16>())).sum();
+            // Mean of i16 values always fits in i16
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            let mean = (sum / channels as i32) as i16;
+            mean
+        })
+        .collect()
 }
 
 impl AudioStream {
@@ -162,6 +249,48 @@ impl AudioStream {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_downmix_mono_passthrough() {
+        let data = [0i16, 100, -100, i16::MAX, i16::MIN];
+        assert_eq!(downmix(&data, 1), data);
+    }
+
+    #[test]
+    fn test_downmix_averages_channels() {
+        let data = [100i16, 300, -200, -400, i16::MAX, i16::MAX];
+        assert_eq!(downmix(&data, 2), vec![200, -300, i16::MAX]);
+
+        // Trailing partial frame is dropped
+        let data = [30i16, 60, 90, 10];
+        assert_eq!(downmix(&data, 3), vec![60]);
+    }
+
+    #[test]
+    fn test_downmix_converts_formats() {
+        assert_eq!(
+            downmix(&[0.0f32, 1.0, -1.0], 1),
+            vec![0, i16::MAX, i16::MIN]
+        );
+        assert_eq!(
+            downmix(&[32768u16, 0, 65535], 1),
+            vec![0, i16::MIN, i16::MAX]
+        );
+        assert_eq!(
+            downmix(&[0i32, i32::MAX, i32::MIN], 1),
+            vec![0, i16::MAX, i16::MIN]
+        );
+
+        // Stereo f32 with opposite channels cancels out
+        assert_eq!(downmix(&[0.5f32, -0.5, 0.5, 0.5], 2), vec![0, 16384]);
+    }
+
+    #[test]
+    fn test_supported_formats() {
+        assert!(is_supported_format(SampleFormat::I16));
+        assert!(is_supported_format(SampleFormat::F32));
+        assert!(!is_supported_format(SampleFormat::U8));
+    }
+
     #[test]
     #[ignore = "requires audio devices - may segfault in CI"]
     fn test_list_devices() {
@@ -1,5 +1,7 @@
 pub mod capture;
+pub mod resample;
 pub mod vad;
 
 pub use capture::{AudioCapture, AudioStream};
-pub use vad::{VadConfig, VoiceActivityDetector};
+pub use resample::{resample, Resampler};
+pub use vad::{SpeechSegmenter, VadConfig, VoiceActivityDetector};
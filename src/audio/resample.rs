@@ -0,0 +1,252 @@
+use std::f64::consts::PI;
+
+/// Number of filter zero crossings on each side of the kernel center
+const ZERO_CROSSINGS: f64 = 16.0;
+
+/// Streaming sample rate converter using a windowed-sinc filter
+///
+/// Audio can be pushed in arbitrarily sized chunks; filter state is kept between
+/// calls so chunked output matches converting the whole signal at once. When
+/// downsampling, the filter cutoff is lowered to the target Nyquist frequency to
+/// avoid aliasing.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    from_rate: u32,
+    to_rate: u32,
+    /// Input samples advanced per output sample
+    step: f64,
+    /// Filter cutoff relative to input Nyquist (<= 1.0)
+    cutoff: f64,
+    /// Kernel half-width in input samples
+    half_width: usize,
+    /// Pending input samples, preceded by filter history
+    buffer: Vec<f64>,
+    /// Input samples dropped from the front of `buffer` so far
+    dropped: usize,
+    /// Number of output samples produced so far
+    produced: usize,
+}
+
+impl Resampler {
+    /// Create new resampler converting `from_rate` to `to_rate`
+    #[must_use]
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        let step = f64::from(from_rate) / f64::from(to_rate);
+        let cutoff = (1.0 / step).min(1.0);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let half_width = (ZERO_CROSSINGS / cutoff).ceil() as usize;
+
+        // Prime with silence so the first output sample is centered on the first input sample
+        Self {
+            from_rate,
+            to_rate,
+            step,
+            cutoff,
+            half_width,
+            buffer: vec![0.0; half_width],
+            dropped: 0,
+            produced: 0,
+        }
+    }
+
+    /// Whether this resampler changes the signal at all
+    #[must_use]
+    pub const fn is_passthrough(&self) -> bool {
+        self.from_rate == self.to_rate
+    }
+
+    /// Convert a chunk of samples, returning all output samples available so far
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.is_passthrough() {
+            return input.to_vec();
+        }
+
+        self.buffer.extend(input.iter().map(|&s| f64::from(s)));
+        self.drain_available()
+    }
+
+    /// Emit remaining output by padding the input with silence, then reset
+    pub fn flush(&mut self) -> Vec<i16> {
+        if self.is_passthrough() {
+            return Vec::new();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let end = self.buffer.len() as f64;
+        self.buffer
+            .extend(std::iter::repeat_n(0.0, self.half_width + 1));
+
+        let output = self.produce_until(end);
+        *self = Self::new(self.from_rate, self.to_rate);
+        output
+    }
+
+    /// Position of the next output sample in `buffer`
+    #[allow(clippy::cast_precision_loss)]
+    fn position(&self) -> f64 {
+        let origin = self.half_width as f64 - self.dropped as f64;
+        (self.produced as f64).mul_add(self.step, origin)
+    }
+
+    /// Produce output samples whose kernel is fully covered by buffered input
+    fn drain_available(&mut self) -> Vec<i16> {
+        #[allow(clippy::cast_precision_loss)]
+        let limit = (self.buffer.len() - self.half_width) as f64;
+        let output = self.produce_until(limit);
+
+        // Drop input no longer needed as filter history
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let consumed = (self.position().floor() as usize).saturating_sub(self.half_width);
+        if consumed > 0 {
+            self.buffer.drain(..consumed);
+            self.dropped += consumed;
+        }
+
+        output
+    }
+
+    /// Produce output samples for all positions before `limit`
+    fn produce_until(&mut self, limit: f64) -> Vec<i16> {
+        let mut output = Vec::new();
+        loop {
+            let position = self.position();
+            if position >= limit {
+                break;
+            }
+            output.push(self.sample_at(position));
+            self.produced += 1;
+        }
+        output
+    }
+
+    /// Evaluate the filtered signal at a fractional buffer position
+    fn sample_at(&self, position: f64) -> i16 {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let center = position.floor() as usize;
+        let first = (center + 1).saturating_sub(self.half_width);
+        let last = (center + self.half_width).min(self.buffer.len() - 1);
+
+        #[allow(clippy::cast_precision_loss)]
+        let half_width = self.half_width as f64;
+
+        let value: f64 = (first..=last)
+            .map(|k| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = position - k as f64;
+                self.buffer[k] * self.kernel(x, half_width)
+            })
+            .sum();
+
+        #[allow(clippy::cast_possible_truncation)]
+        let sample = value
+            .round()
+            .clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16;
+        sample
+    }
+
+    /// Hann-windowed, band-limited sinc kernel
+    fn kernel(&self, x: f64, half_width: f64) -> f64 {
+        if x.abs() >= half_width {
+            return 0.0;
+        }
+
+        let arg = PI * self.cutoff * x;
+        let sinc = if arg.abs() < 1e-9 {
+            1.0
+        } else {
+            arg.sin() / arg
+        };
+        let window = 0.5 * (1.0 + (PI * x / half_width).cos());
+
+        self.cutoff * sinc * window
+    }
+}
+
+/// Convert a complete buffer of samples from one rate to another
+#[must_use]
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    let mut resampler = Resampler::new(from_rate, to_rate);
+    let mut output = resampler.process(samples);
+    output.extend(resampler.flush());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn sine(freq: f64, rate: u32, len: usize, amplitude: f64) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / f64::from(rate);
+                (amplitude * (2.0 * PI * freq * t).sin()) as i16
+            })
+            .collect()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rms(samples: &[i16]) -> f64 {
+        let sum: f64 = samples.iter().map(|&s| f64::from(s).powi(2)).sum();
+        (sum / samples.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn test_passthrough() {
+        let samples = vec![1i16, -2, 3, -4, 5];
+        assert_eq!(resample(&samples, 16000, 16000), samples);
+        assert!(Resampler::new(16000, 16000).is_passthrough());
+    }
+
+    #[test]
+    fn test_output_length() {
+        let samples = vec![0i16; 48000];
+        assert_eq!(resample(&samples, 48000, 16000).len(), 16000);
+        assert_eq!(resample(&samples, 44100, 16000).len(), 17415);
+
+        let samples = vec![0i16; 8000];
+        assert_eq!(resample(&samples, 8000, 16000).len(), 16000);
+    }
+
+    #[test]
+    fn test_preserves_in_band_tone() {
+        // 440 Hz tone should survive 48k -> 16k with its amplitude intact
+        let input = sine(440.0, 48000, 48000, 10000.0);
+        let output = resample(&input, 48000, 16000);
+        let expected = sine(440.0, 16000, 16000, 10000.0);
+
+        // Ignore edges where the filter sees padding
+        let middle = 1000..15000;
+        let max_error = output[middle.clone()]
+            .iter()
+            .zip(&expected[middle])
+            .map(|(&a, &b)| (i32::from(a) - i32::from(b)).abs())
+            .max()
+            .unwrap();
+        assert!(max_error < 200, "max error {max_error}");
+    }
+
+    #[test]
+    fn test_attenuates_above_nyquist() {
+        // 12 kHz cannot be represented at 16 kHz and must not alias into the output
+        let input = sine(12000.0, 48000, 48000, 10000.0);
+        let output = resample(&input, 48000, 16000);
+        assert!(rms(&output[1000..15000]) < 200.0);
+    }
+
+    #[test]
+    fn test_chunked_matches_one_shot() {
+        let input = sine(1000.0, 44100, 44100, 8000.0);
+        let one_shot = resample(&input, 44100, 16000);
+
+        let mut resampler = Resampler::new(44100, 16000);
+        let mut chunked = Vec::new();
+        for chunk in input.chunks(1323) {
+            chunked.extend(resampler.process(chunk));
+        }
+        chunked.extend(resampler.flush());
+
+        assert_eq!(chunked, one_shot);
+    }
+}
@@ -12,6 +12,19 @@ pub struct VoiceActivityDetector {
     min_duration_ms: u32,
 }
 
+/// Incremental speech segmenter built on [`VoiceActivityDetector`]
+///
+/// Frames are pushed one at a time as they are captured. An utterance is
+/// emitted once `silence_ms` of silence follows detected speech, so callers
+/// can act on speech while recording continues.
+pub struct SpeechSegmenter {
+    vad: VoiceActivityDetector,
+    utterance: Vec<i16>,
+    speech_detected: bool,
+    silence_count: u32,
+    skip_remaining: u32,
+}
+
 /// Configuration for VAD
 #[derive(Debug, Clone)]
 pub struct VadConfig {
@@ -144,22 +157,39 @@ impl VoiceActivityDetector {
         }
 
         // Check minimum duration
-        if !speech_detected {
+        if !speech_detected || !self.meets_min_duration(&recording) {
             return Ok(None);
         }
 
+        Ok(Some(recording))
+    }
+
+    /// Check whether any frame of a recording contains speech
+    ///
+    /// Initial frames are skipped as in [`Self::extract_speech_from_frames`].
+    pub fn contains_speech<'a, I>(&mut self, frames: I) -> Result<bool>
+    where
+        I: IntoIterator<Item = &'a [i16]>,
+    {
+        for frame in frames.into_iter().skip(self.skip_initial_frames as usize) {
+            if self.is_voice_frame(frame)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Check whether a speech segment meets the minimum duration
+    fn meets_min_duration(&self, samples: &[i16]) -> bool {
         #[allow(
             clippy::cast_precision_loss,
             clippy::cast_possible_truncation,
             clippy::cast_sign_loss
         )]
-        let duration_ms = ((recording.len() as f64 / f64::from(self.sample_rate)) * 1000.0) as u32;
-
-        if duration_ms < self.min_duration_ms {
-            return Ok(None);
-        }
+        let duration_ms = ((samples.len() as f64 / f64::from(self.sample_rate)) * 1000.0) as u32;
 
-        Ok(Some(recording))
+        duration_ms >= self.min_duration_ms
     }
 
     /// Get the expected frame size for this VAD
@@ -181,10 +211,96 @@ impl VoiceActivityDetector {
     }
 }
 
+impl SpeechSegmenter {
+    /// Create new segmenter with specified configuration
+    pub fn new(config: &VadConfig) -> Result<Self> {
+        let vad = VoiceActivityDetector::new(config)?;
+        let skip_remaining = vad.skip_initial_frames;
+
+        Ok(Self {
+            vad,
+            utterance: Vec::new(),
+            speech_detected: false,
+            silence_count: 0,
+            skip_remaining,
+        })
+    }
+
+    /// Process a single frame
+    ///
+    /// Returns `Ok(Some(audio))` when an utterance has ended (speech followed by silence)
+    /// Returns `Ok(None)` while no utterance is complete, or if it was too short
+    /// Returns `Err` on VAD processing errors
+    pub fn push_frame(&mut self, frame: &[i16]) -> Result<Option<Vec<i16>>> {
+        // Skip initial frames to avoid keyboard noise
+        if self.skip_remaining > 0 {
+            self.skip_remaining -= 1;
+            return Ok(None);
+        }
+
+        let is_speech = self.vad.is_voice_frame(frame)?;
+
+        if is_speech {
+            self.silence_count = 0;
+            self.speech_detected = true;
+            self.utterance.extend_from_slice(frame);
+        } else if self.speech_detected {
+            self.silence_count += 1;
+            self.utterance.extend_from_slice(frame);
+
+            if self.silence_count >= self.vad.silence_threshold_frames {
+                return Ok(self.flush());
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Take any in-progress utterance, e.g. when recording stops
+    ///
+    /// Returns `None` if no speech was detected or the utterance is too short
+    pub fn flush(&mut self) -> Option<Vec<i16>> {
+        let utterance = std::mem::take(&mut self.utterance);
+        let speech_detected = std::mem::replace(&mut self.speech_detected, false);
+        self.silence_count = 0;
+
+        (speech_detected && self.vad.meets_min_duration(&utterance)).then_some(utterance)
+    }
+
+    /// Whether speech has been detected in the current utterance
+    #[must_use]
+    pub const fn in_speech(&self) -> bool {
+        self.speech_detected
+    }
+
+    /// Get the expected frame size
+    #[must_use]
+    pub const fn frame_size(&self) -> usize {
+        self.vad.frame_size
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Generate a 30ms voiced frame (150 Hz harmonic series) at 16kHz
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn voiced_frame(index: usize) -> Vec<i16> {
+        (0..480)
+            .map(|i| {
+                let t = (index * 480 + i) as f32 / 16000.0;
+                let v: f32 = (1..=10)
+                    .map(|h| {
+                        let h = h as f32;
+                        (2.0 * std::f32::consts::PI * 150.0 * h * t).sin() / h
+                    })
+                    .sum();
+                (v * 6000.0) as i16
+            })
+            .collect()
+    }
+
     #[test]
     fn test_vad_config_default() {
         let config = VadConfig::default_16khz();
@@ -256,6 +372,28 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_contains_speech() {
+        let config = VadConfig::default_16khz();
+        let mut vad = VoiceActivityDetector::new(&config).unwrap();
+
+        let mut frames: Vec<Vec<i16>> = (0..50).map(|_| vec![0i16; 480]).collect();
+        assert!(!vad
+            .contains_speech(frames.iter().map(Vec::as_slice))
+            .unwrap());
+
+        frames.extend((0..10).map(voiced_frame));
+        assert!(vad
+            .contains_speech(frames.iter().map(Vec::as_slice))
+            .unwrap());
+
+        // Speech only within the skipped initial frames does not count
+        let frames: Vec<Vec<i16>> = (0..5).map(voiced_frame).collect();
+        assert!(!vad
+            .contains_speech(frames.iter().map(Vec::as_slice))
+            .unwrap());
+    }
+
     #[test]
     fn test_extract_speech_with_noise() {
         let config = VadConfig::default_16khz();
@@ -284,4 +422,75 @@ mod tests {
         // Just verify it doesn't panic - result may be Some or None
         let _result = vad.extract_speech_from_frames(frames).unwrap();
     }
+
+    #[test]
+    fn test_segmenter_silence() {
+        let config = VadConfig::default_16khz();
+        let mut segmenter = SpeechSegmenter::new(&config).unwrap();
+        assert_eq!(segmenter.frame_size(), 480);
+
+        for _ in 0..100 {
+            assert!(segmenter.push_frame(&[0i16; 480]).unwrap().is_none());
+        }
+        assert!(!segmenter.in_speech());
+        assert!(segmenter.flush().is_none());
+    }
+
+    #[test]
+    fn test_segmenter_invalid_frame_size() {
+        let config = VadConfig {
+            skip_initial_ms: 0,
+            ..VadConfig::default_16khz()
+        };
+        let mut segmenter = SpeechSegmenter::new(&config).unwrap();
+        assert!(segmenter.push_frame(&[0i16; 100]).is_err());
+    }
+
+    #[test]
+    fn test_segmenter_emits_utterances() {
+        let config = VadConfig {
+            skip_initial_ms: 0,
+            ..VadConfig::default_16khz()
+        };
+        let silence_frames = (config.silence_ms / 30) as usize;
+        let mut segmenter = SpeechSegmenter::new(&config).unwrap();
+
+        // Two utterances of 1.2s speech, each followed by enough silence to end it
+        // (WebRTC VAD keeps reporting speech for a few frames after it stops)
+        for _ in 0..2 {
+            for i in 0..40 {
+                assert!(segmenter.push_frame(&voiced_frame(i)).unwrap().is_none());
+            }
+            assert!(segmenter.in_speech());
+
+            let utterance = (0..silence_frames * 2)
+                .find_map(|_| segmenter.push_frame(&[0i16; 480]).unwrap())
+                .expect("utterance should end after silence");
+            assert!(!segmenter.in_speech());
+            assert!(utterance.len() >= (40 + silence_frames) * 480);
+            assert!(utterance.len() < (40 + silence_frames * 2) * 480);
+        }
+    }
+
+    #[test]
+    fn test_segmenter_flush_partial_utterance() {
+        let config = VadConfig {
+            skip_initial_ms: 0,
+            ..VadConfig::default_16khz()
+        };
+        let mut segmenter = SpeechSegmenter::new(&config).unwrap();
+
+        // Too short to meet min_duration_ms (500ms)
+        for i in 0..5 {
+            segmenter.push_frame(&voiced_frame(i)).unwrap();
+        }
+        assert!(segmenter.flush().is_none());
+
+        // Long enough
+        for i in 0..30 {
+            segmenter.push_frame(&voiced_frame(i)).unwrap();
+        }
+        assert_eq!(segmenter.flush().map(|u| u.len()), Some(30 * 480));
+        assert!(segmenter.flush().is_none());
+    }
 }
@@ -1,5 +1,10 @@
 use crate::error::{Result, ScribeError};
+use crate::models::quantize::QUANTIZATIONS;
+use crate::models::ModelSource;
+use crate::postprocess::PostProcessor;
+use crate::transcription::providers;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -8,8 +13,12 @@ use std::path::PathBuf;
 pub struct Config {
     pub audio: AudioConfig,
     pub vad: VadConfig,
+    #[serde(default)]
+    pub recording: RecordingConfig,
     pub transcription: TranscriptionConfig,
     pub injection: InjectionConfig,
+    #[serde(default)]
+    pub postprocess: PostprocessConfig,
     pub notifications: NotificationConfig,
     pub logging: LoggingConfig,
 }
@@ -33,9 +42,17 @@ pub struct VadConfig {
     pub skip_initial_ms: u32,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RecordingConfig {
+    /// Recording mode: "manual" (transcribe on stop), "streaming" (transcribe each utterance)
+    /// or `"auto_stop"` (stop and transcribe once speech is followed by silence)
+    #[serde(default = "default_recording_mode")]
+    pub mode: String,
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct TranscriptionConfig {
-    /// Backend type: "local" or "openai"
+    /// Backend type: "local" or a remote provider ("openai", "groq", "http")
     #[serde(default = "default_backend")]
     pub backend: String,
 
@@ -46,19 +63,59 @@ pub struct TranscriptionConfig {
     /// Device for local backend: "cpu", "cuda", "auto"
     #[serde(default = "default_device")]
     pub device: String,
+    /// Weight quantization for local backend: "none", `"q4_0"`, `"q5_0"`, `"q8_0"`
+    #[serde(default = "default_quantization")]
+    pub quantization: String,
     /// Language code (e.g., "en", "es", "fr") - leave empty for auto-detect
     #[serde(default = "default_language")]
     pub language: String,
+    /// Task: "transcribe" (keep the spoken language) or "translate" (into English)
+    #[serde(default = "default_task")]
+    pub task: String,
     /// Initial prompt for better context (optional)
     pub initial_prompt: Option<String>,
+    /// Beams kept during local beam search (1 = greedy decoding)
+    #[serde(default = "default_beam_size")]
+    pub beam_size: usize,
+    /// Beam search patience: finished candidates collected, relative to `beam_size`
+    #[serde(default = "default_patience")]
+    pub patience: f32,
+    /// Temperature step when retrying a failed decode (0 disables fallback)
+    #[serde(default = "default_temperature_increment")]
+    pub temperature_increment: f32,
+    /// Retry decoding when average token log-probability is below this
+    #[serde(default = "default_logprob_threshold")]
+    pub logprob_threshold: f32,
+    /// Retry decoding when text compresses better than this (repetition loops)
+    #[serde(default = "default_compression_ratio_threshold")]
+    pub compression_ratio_threshold: f32,
+    /// Discard segments whose no-speech probability is above this (1.0 disables)
+    #[serde(default = "default_no_speech_threshold")]
+    pub no_speech_threshold: f32,
+    /// Decode segment timestamps with the local backend
+    #[serde(default)]
+    pub timestamps: bool,
 
     // OpenAI API backend settings
     /// Environment variable containing API key
     pub api_key_env: Option<String>,
     /// `OpenAI` model name (default: "whisper-1")
     pub api_model: Option<String>,
+    /// Base URL of an OpenAI-compatible API (default: `https://api.openai.com/v1`)
+    pub api_base_url: Option<String>,
+    /// Sampling temperature sent to the API (default: 0)
+    pub api_temperature: Option<f32>,
     /// API request timeout in seconds
     pub api_timeout_secs: Option<u64>,
+    /// Retries after a network error, rate limit or server error (default: 2)
+    pub api_max_retries: Option<u32>,
+    /// Delay before the first retry in milliseconds, doubled for each further retry (default: 500)
+    pub api_retry_delay_ms: Option<u64>,
+    /// Backend that transcribes the audio when a remote backend fails: "local"
+    pub fallback_backend: Option<String>,
+    /// Provider settings tables, e.g. `[transcription.providers.groq]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, toml::Table>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -67,6 +124,51 @@ pub struct InjectionConfig {
     pub method: String,
     #[serde(default = "default_delay_ms")]
     pub delay_ms: u64,
+    /// XKB layout used by the uinput method, e.g. "us" or "de(nodeadkeys)"
+    #[serde(default = "default_layout")]
+    pub layout: String,
+}
+
+/// Text processing between transcription and injection
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PostprocessConfig {
+    /// Turn spoken commands ("comma", "new line", "delete that") into punctuation and key presses
+    #[serde(default)]
+    pub voice_commands: bool,
+    /// Spoken phrases added to, or replacing, the built-in voice commands
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub phrases: BTreeMap<String, PhraseAction>,
+    /// `[[postprocess.replace]]` rules, applied in order before voice commands
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replace: Vec<ReplaceRule>,
+}
+
+/// Rewrite of a systematic mis-transcription
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReplaceRule {
+    /// Text to find, or a regular expression if `regex` is set
+    pub pattern: String,
+    /// Replacement; regex rules may refer to groups as `$1` or `${name}`
+    pub replacement: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Only match whole words, so "cat" leaves "concatenate" alone
+    #[serde(default)]
+    pub whole_word: bool,
+}
+
+/// What a spoken phrase does: `"text"`, `{ key = "ctrl+s" }` or `{ action = "delete_last" }`
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PhraseAction {
+    /// Type the text
+    Text(String),
+    /// Press a key combination such as "ctrl+shift+t" or "enter"
+    Key { key: String },
+    /// Run a built-in action
+    Action { action: String },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -106,6 +208,9 @@ const fn default_min_duration_ms() -> u32 {
 const fn default_skip_initial_ms() -> u32 {
     150
 }
+fn default_recording_mode() -> String {
+    "manual".to_string()
+}
 fn default_backend() -> String {
     "local".to_string()
 }
@@ -115,15 +220,42 @@ fn default_model() -> String {
 fn default_device() -> String {
     "auto".to_string()
 }
+fn default_quantization() -> String {
+    "none".to_string()
+}
 fn default_language() -> String {
     "en".to_string()
 }
+fn default_task() -> String {
+    "transcribe".to_string()
+}
+const fn default_beam_size() -> usize {
+    1
+}
+const fn default_patience() -> f32 {
+    1.0
+}
+const fn default_temperature_increment() -> f32 {
+    0.2
+}
+const fn default_logprob_threshold() -> f32 {
+    -1.0
+}
+const fn default_compression_ratio_threshold() -> f32 {
+    2.4
+}
+const fn default_no_speech_threshold() -> f32 {
+    0.6
+}
 fn default_method() -> String {
-    "dotool".to_string()
+    "auto".to_string()
 }
 const fn default_delay_ms() -> u64 {
     2
 }
+fn default_layout() -> String {
+    "us".to_string()
+}
 const fn default_true() -> bool {
     true
 }
@@ -134,6 +266,14 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_recording_mode(),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -147,20 +287,38 @@ impl Default for Config {
                 min_duration_ms: default_min_duration_ms(),
                 skip_initial_ms: default_skip_initial_ms(),
             },
+            recording: RecordingConfig::default(),
             transcription: TranscriptionConfig {
                 backend: default_backend(),
                 model: default_model(),
                 device: default_device(),
+                quantization: default_quantization(),
                 language: default_language(),
+                task: default_task(),
                 initial_prompt: None,
+                beam_size: default_beam_size(),
+                patience: default_patience(),
+                temperature_increment: default_temperature_increment(),
+                logprob_threshold: default_logprob_threshold(),
+                compression_ratio_threshold: default_compression_ratio_threshold(),
+                no_speech_threshold: default_no_speech_threshold(),
+                timestamps: false,
                 api_key_env: Some("OPENAI_API_KEY".to_string()),
                 api_model: Some("whisper-1".to_string()),
+                api_base_url: None,
+                api_temperature: None,
                 api_timeout_secs: Some(30),
+                api_max_retries: Some(2),
+                api_retry_delay_ms: Some(500),
+                fallback_backend: None,
+                providers: BTreeMap::new(),
             },
             injection: InjectionConfig {
                 method: default_method(),
                 delay_ms: default_delay_ms(),
+                layout: default_layout(),
             },
+            postprocess: PostprocessConfig::default(),
             notifications: NotificationConfig {
                 enable_status: default_true(),
                 enable_errors: default_true(),
@@ -213,8 +371,10 @@ impl Config {
     pub fn validate(&self) -> Result<()> {
         self.validate_audio()?;
         self.validate_vad()?;
+        self.validate_recording()?;
         self.validate_transcription()?;
         self.validate_injection()?;
+        self.validate_postprocess()?;
         self.validate_notifications()?;
         self.validate_logging()?;
         Ok(())
@@ -275,33 +435,137 @@ impl Config {
         Ok(())
     }
 
+    fn validate_recording(&self) -> Result<()> {
+        const VALID_MODES: &[&str] = &["manual", "streaming", "auto_stop"];
+        if !VALID_MODES.contains(&self.recording.mode.as_str()) {
+            return Err(ScribeError::Config(format!(
+                "Invalid recording mode: '{}'. Must be one of: {:?}",
+                self.recording.mode, VALID_MODES
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_decoding(&self) -> Result<()> {
+        let t = &self.transcription;
+
+        if !(1..=16).contains(&t.beam_size) {
+            return Err(ScribeError::Config(format!(
+                "Invalid beam_size: {}. Must be between 1 and 16",
+                t.beam_size
+            )));
+        }
+
+        if !(t.patience > 0.0 && t.patience <= 10.0) {
+            return Err(ScribeError::Config(format!(
+                "Invalid patience: {}. Must be greater than 0 and at most 10",
+                t.patience
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&t.temperature_increment) {
+            return Err(ScribeError::Config(format!(
+                "Invalid temperature_increment: {}. Must be between 0.0 and 1.0",
+                t.temperature_increment
+            )));
+        }
+
+        if !(t.logprob_threshold.is_finite() && t.logprob_threshold <= 0.0) {
+            return Err(ScribeError::Config(format!(
+                "Invalid logprob_threshold: {}. Must be 0 or negative",
+                t.logprob_threshold
+            )));
+        }
+
+        if !(t.compression_ratio_threshold.is_finite() && t.compression_ratio_threshold > 0.0) {
+            return Err(ScribeError::Config(format!(
+                "Invalid compression_ratio_threshold: {}. Must be greater than 0",
+                t.compression_ratio_threshold
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&t.no_speech_threshold) {
+            return Err(ScribeError::Config(format!(
+                "Invalid no_speech_threshold: {}. Must be between 0.0 and 1.0",
+                t.no_speech_threshold
+            )));
+        }
+
+        Ok(())
+    }
+
     fn validate_transcription(&self) -> Result<()> {
-        const VALID_BACKENDS: &[&str] = &["local", "openai"];
-        const VALID_MODELS: &[&str] = &["tiny", "base", "small", "medium", "large"];
         const VALID_DEVICES: &[&str] = &["cpu", "cuda", "auto"];
+        const VALID_TASKS: &[&str] = &["transcribe", "translate"];
 
-        if !VALID_BACKENDS.contains(&self.transcription.backend.as_str()) {
+        let provider = providers::find(&self.transcription.backend);
+        if self.transcription.backend != "local" && provider.is_none() {
             return Err(ScribeError::Config(format!(
-                "Invalid backend: '{}'. Must be one of: {:?}",
-                self.transcription.backend, VALID_BACKENDS
+                "Invalid backend: '{}'. Must be \"local\" or one of: {:?}",
+                self.transcription.backend,
+                providers::names()
             )));
         }
 
-        // Validate local backend settings
-        if self.transcription.backend == "local" {
-            if !VALID_MODELS.contains(&self.transcription.model.as_str()) {
+        let local_fallback = self.transcription.fallback_backend.as_deref() == Some("local");
+        if let Some(fallback) = &self.transcription.fallback_backend {
+            if !local_fallback || self.transcription.backend == "local" {
+                return Err(ScribeError::Config(format!(
+                    "Invalid fallback_backend: '{fallback}'. Only \"local\" is supported, as a fallback for remote backends"
+                )));
+            }
+        }
+
+        // Validate local backend settings, also used by the local fallback
+        if self.transcription.backend == "local" || local_fallback {
+            // Registry name, HuggingFace repo id or local directory
+            let source = ModelSource::parse(&self.transcription.model).map_err(|e| {
+                ScribeError::Config(format!(
+                    "Invalid model: '{}'. {e}",
+                    self.transcription.model
+                ))
+            })?;
+
+            if self.transcription.quantization != "none"
+                && !QUANTIZATIONS.contains(&self.transcription.quantization.as_str())
+            {
                 return Err(ScribeError::Config(format!(
-                    "Invalid model: '{}'. Must be one of: {:?}",
-                    self.transcription.model, VALID_MODELS
+                    "Invalid quantization: '{}'. Must be \"none\" or one of: {:?}",
+                    self.transcription.quantization, QUANTIZATIONS
                 )));
             }
 
+            if let ModelSource::Registry(info) = source {
+                if !info.multilingual && !matches!(self.transcription.language.as_str(), "" | "en")
+                {
+                    return Err(ScribeError::Config(format!(
+                        "Model '{}' is English-only but language is '{}'. Set language = \"en\" or use a multilingual model",
+                        info.name, self.transcription.language
+                    )));
+                }
+                if !info.multilingual && self.transcription.task == "translate" {
+                    return Err(ScribeError::Config(format!(
+                        "Model '{}' is English-only and cannot translate. Use a multilingual model",
+                        info.name
+                    )));
+                }
+            }
+
             if !VALID_DEVICES.contains(&self.transcription.device.as_str()) {
                 return Err(ScribeError::Config(format!(
                     "Invalid device: '{}'. Must be one of: {:?}",
                     self.transcription.device, VALID_DEVICES
                 )));
             }
+
+            self.validate_decoding()?;
+        }
+
+        if !VALID_TASKS.contains(&self.transcription.task.as_str()) {
+            return Err(ScribeError::Config(format!(
+                "Invalid task: '{}'. Must be one of: {:?}",
+                self.transcription.task, VALID_TASKS
+            )));
         }
 
         // Validate language if provided
@@ -312,19 +576,54 @@ impl Config {
             )));
         }
 
-        // Validate OpenAI backend settings
-        if self.transcription.backend == "openai" {
-            if let Some(timeout) = self.transcription.api_timeout_secs {
-                if timeout == 0 {
-                    return Err(ScribeError::Config(
-                        "api_timeout_secs must be greater than 0".to_string(),
-                    ));
-                }
-                if timeout > 300 {
-                    return Err(ScribeError::Config(format!(
-                        "api_timeout_secs too large: {timeout}. Should be < 300s"
-                    )));
-                }
+        // Validate remote provider settings
+        if let Some(provider) = provider {
+            self.validate_api()?;
+            provider.validate(&self.transcription)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_api(&self) -> Result<()> {
+        if let Some(timeout) = self.transcription.api_timeout_secs {
+            if timeout == 0 {
+                return Err(ScribeError::Config(
+                    "api_timeout_secs must be greater than 0".to_string(),
+                ));
+            }
+            if timeout > 300 {
+                return Err(ScribeError::Config(format!(
+                    "api_timeout_secs too large: {timeout}. Should be < 300s"
+                )));
+            }
+        }
+        if let Some(url) = &self.transcription.api_base_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(ScribeError::Config(format!(
+                    "Invalid api_base_url: '{url}'. Must start with http:// or https://"
+                )));
+            }
+        }
+        if let Some(retries) = self.transcription.api_max_retries {
+            if retries > 10 {
+                return Err(ScribeError::Config(format!(
+                    "api_max_retries too large: {retries}. Should be <= 10"
+                )));
+            }
+        }
+        if let Some(delay_ms) = self.transcription.api_retry_delay_ms {
+            if delay_ms == 0 || delay_ms > 60_000 {
+                return Err(ScribeError::Config(format!(
+                    "api_retry_delay_ms must be between 1 and 60000, got {delay_ms}"
+                )));
+            }
+        }
+        if let Some(temperature) = self.transcription.api_temperature {
+            if !(0.0..=1.0).contains(&temperature) {
+                return Err(ScribeError::Config(format!(
+                    "api_temperature must be between 0 and 1, got {temperature}"
+                )));
             }
         }
 
@@ -332,7 +631,15 @@ impl Config {
     }
 
     fn validate_injection(&self) -> Result<()> {
-        const VALID_METHODS: &[&str] = &["dotool"];
+        const VALID_METHODS: &[&str] = &[
+            "auto",
+            "uinput",
+            "dotool",
+            "ydotool",
+            "wtype",
+            "xdotool",
+            "clipboard",
+        ];
         if !VALID_METHODS.contains(&self.injection.method.as_str()) {
             return Err(ScribeError::Config(format!(
                 "Invalid injection method: '{}'. Must be one of: {:?}",
@@ -347,9 +654,35 @@ impl Config {
             )));
         }
 
+        // "layout" or "layout(variant)", naming a file in the XKB symbols directory
+        let (file, variant) = self
+            .injection
+            .layout
+            .strip_suffix(')')
+            .and_then(|layout| layout.split_once('('))
+            .unwrap_or((self.injection.layout.as_str(), "x"));
+        let is_name = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        if !is_name(file) || !is_name(variant) {
+            return Err(ScribeError::Config(format!(
+                "Invalid injection layout: '{}'. Use an XKB layout name such as \"us\" or \"de(nodeadkeys)\"",
+                self.injection.layout
+            )));
+        }
+
         Ok(())
     }
 
+    fn validate_postprocess(&self) -> Result<()> {
+        // Building the processor compiles every replacement and checks every
+        // phrase and key combination
+        PostProcessor::from_config(&self.postprocess).map(|_| ())
+    }
+
     fn validate_notifications(&self) -> Result<()> {
         if self.notifications.preview_length == 0 {
             return Err(ScribeError::Config(
@@ -398,10 +731,13 @@ mod tests {
         assert_eq!(config.vad.silence_ms, 900);
         assert_eq!(config.vad.min_duration_ms, 500);
         assert_eq!(config.vad.skip_initial_ms, 150);
+        assert_eq!(config.recording.mode, "manual");
         assert_eq!(config.transcription.backend, "local");
         assert_eq!(config.transcription.model, "base");
         assert_eq!(config.transcription.language, "en");
-        assert_eq!(config.injection.method, "dotool");
+        assert_eq!(config.transcription.quantization, "none");
+        assert_eq!(config.injection.method, "auto");
+        assert_eq!(config.injection.layout, "us");
         assert_eq!(config.injection.delay_ms, 2);
         assert!(config.notifications.enable_status);
         assert!(config.notifications.enable_errors);
@@ -498,6 +834,27 @@ mod tests {
         assert!(config.validate_vad().is_err());
     }
 
+    #[test]
+    fn test_valid_recording_modes() {
+        for mode in &["manual", "streaming", "auto_stop"] {
+            let mut config = Config::default();
+            config.recording.mode = mode.to_string();
+            assert!(config.validate_recording().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_recording_mode() {
+        let mut config = Config::default();
+        config.recording.mode = "invalid".to_string();
+        let result = config.validate_recording();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid recording mode"));
+    }
+
     #[test]
     fn test_valid_transcription_backends() {
         for backend in &["local", "openai"] {
@@ -507,6 +864,102 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "provider-groq", feature = "provider-http"))]
+    #[test]
+    fn test_provider_backends() {
+        let mut config = Config::default();
+        config.transcription.backend = "groq".to_string();
+        assert!(config.validate_transcription().is_ok());
+
+        // The generic provider has no default URL
+        config.transcription.backend = "http".to_string();
+        let result = config.validate_transcription();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("[transcription.providers.http]"));
+
+        config.transcription.providers.insert(
+            "http".to_string(),
+            toml::from_str("url = \"https://stt.example.com/v1/listen\"").unwrap(),
+        );
+        assert!(config.validate_transcription().is_ok());
+    }
+
+    #[test]
+    fn test_postprocess_phrases() {
+        let postprocess: PostprocessConfig = toml::from_str(
+            r#"
+            voice_commands = true
+
+            [phrases]
+            "smiley face" = ":-)"
+            "save file" = { key = "ctrl+s" }
+            "never mind" = { action = "delete_last" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            postprocess.phrases["smiley face"],
+            PhraseAction::Text(":-)".to_string())
+        );
+        assert_eq!(
+            postprocess.phrases["save file"],
+            PhraseAction::Key {
+                key: "ctrl+s".to_string()
+            }
+        );
+
+        let mut config = Config::default();
+        assert!(!config.postprocess.voice_commands);
+        config.postprocess = postprocess;
+        assert!(config.validate_postprocess().is_ok());
+
+        config.postprocess.phrases.insert(
+            "open tab".to_string(),
+            PhraseAction::Key {
+                key: "ctrl+hyper+t".to_string(),
+            },
+        );
+        let result = config.validate_postprocess();
+        assert!(result.unwrap_err().to_string().contains("open tab"));
+    }
+
+    #[test]
+    fn test_postprocess_replace() {
+        let postprocess: PostprocessConfig = toml::from_str(
+            r#"
+            [[replace]]
+            pattern = "whisker"
+            replacement = "Whisper"
+            whole_word = true
+
+            [[replace]]
+            pattern = '(\d+) percent'
+            replacement = "$1%"
+            regex = true
+            ignore_case = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(postprocess.replace.len(), 2);
+        assert!(!postprocess.replace[0].regex);
+        assert!(postprocess.replace[0].whole_word);
+        assert!(postprocess.replace[1].ignore_case);
+
+        let mut config = Config::default();
+        assert!(config.postprocess.replace.is_empty());
+        config.postprocess = postprocess;
+        assert!(config.validate().is_ok());
+
+        config.postprocess.replace[1].pattern = "(\\d+ percent".to_string();
+        let result = config.validate();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid [[postprocess.replace]] pattern"));
+    }
+
     #[test]
     fn test_invalid_transcription_backend() {
         let mut config = Config::default();
@@ -525,6 +978,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_quantization_validation() {
+        for quantization in &["none", "q4_0", "q5_0", "q8_0"] {
+            let mut config = Config::default();
+            config.transcription.quantization = quantization.to_string();
+            assert!(config.validate_transcription().is_ok());
+        }
+
+        let mut config = Config::default();
+        config.transcription.quantization = "q3".to_string();
+        let result = config.validate_transcription();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid quantization"));
+    }
+
+    #[test]
+    fn test_decoding_validation() {
+        let mut config = Config::default();
+        config.transcription.beam_size = 5;
+        config.transcription.patience = 2.0;
+        config.transcription.temperature_increment = 0.0;
+        assert!(config.validate_transcription().is_ok());
+
+        let invalid: [fn(&mut TranscriptionConfig); 6] = [
+            |t| t.beam_size = 0,
+            |t| t.patience = 0.0,
+            |t| t.temperature_increment = 1.5,
+            |t| t.logprob_threshold = 0.5,
+            |t| t.compression_ratio_threshold = f32::NAN,
+            |t| t.no_speech_threshold = 1.5,
+        ];
+        for update in invalid {
+            let mut config = Config::default();
+            update(&mut config.transcription);
+            assert!(config.validate_transcription().is_err());
+        }
+    }
+
+    #[test]
+    fn test_english_only_models_require_english() {
+        for model in &["tiny.en", "base.en", "distil-medium.en"] {
+            let mut config = Config::default();
+            config.transcription.model = model.to_string();
+            config.transcription.language = "en".to_string();
+            assert!(config.validate_transcription().is_ok());
+
+            config.transcription.language = String::new();
+            assert!(config.validate_transcription().is_ok());
+
+            config.transcription.language = "de".to_string();
+            let result = config.validate_transcription();
+            assert!(result.unwrap_err().to_string().contains("English-only"));
+        }
+
+        let mut config = Config::default();
+        config.transcription.model = "large-v3-turbo".to_string();
+        config.transcription.language = "de".to_string();
+        assert!(config.validate_transcription().is_ok());
+    }
+
+    #[test]
+    fn test_distil_large_v3_is_english_only() {
+        let mut config = Config::default();
+        config.transcription.model = "distil-large-v3".to_string();
+        assert!(config.validate_transcription().is_ok());
+
+        config.transcription.language = "de".to_string();
+        let result = config.validate_transcription();
+        assert!(result.unwrap_err().to_string().contains("English-only"));
+
+        config.transcription.language = "en".to_string();
+        config.transcription.task = "translate".to_string();
+        let result = config.validate_transcription();
+        assert!(result.unwrap_err().to_string().contains("cannot translate"));
+    }
+
+    #[test]
+    fn test_task_validation() {
+        let mut config = Config::default();
+        config.transcription.task = "translate".to_string();
+        assert!(config.validate_transcription().is_ok());
+
+        config.transcription.model = "base.en".to_string();
+        let result = config.validate_transcription();
+        assert!(result.unwrap_err().to_string().contains("cannot translate"));
+
+        config.transcription.model = "base".to_string();
+        config.transcription.task = "summarize".to_string();
+        assert!(config.validate_transcription().is_err());
+    }
+
+    #[test]
+    fn test_custom_transcription_models() {
+        for model in &[
+            "acme/whisper-medical",
+            "acme/whisper-medical@v2",
+            "/opt/models/whisper-medical",
+            "~/models/whisper-medical",
+        ] {
+            let mut config = Config::default();
+            config.transcription.model = model.to_string();
+            assert!(config.validate_transcription().is_ok(), "{model}");
+        }
+
+        let mut config = Config::default();
+        config.transcription.model = "acme/whisper/medical".to_string();
+        assert!(config.validate_transcription().is_err());
+    }
+
     #[test]
     fn test_invalid_transcription_model() {
         let mut config = Config::default();
@@ -534,6 +1098,50 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Invalid model"));
     }
 
+    #[test]
+    fn test_api_settings_validation() {
+        let mut config = Config::default();
+        config.transcription.backend = "openai".to_string();
+        config.transcription.api_base_url = Some("http://localhost:8000/v1".to_string());
+        config.transcription.api_temperature = Some(0.2);
+        assert!(config.validate_transcription().is_ok());
+
+        config.transcription.api_base_url = Some("localhost:8000/v1".to_string());
+        assert!(config.validate_transcription().is_err());
+
+        config.transcription.api_base_url = None;
+        config.transcription.api_temperature = Some(1.5);
+        assert!(config.validate_transcription().is_err());
+
+        config.transcription.api_temperature = None;
+        config.transcription.api_max_retries = Some(11);
+        assert!(config.validate_transcription().is_err());
+
+        config.transcription.api_max_retries = Some(3);
+        config.transcription.api_retry_delay_ms = Some(0);
+        assert!(config.validate_transcription().is_err());
+    }
+
+    #[test]
+    fn test_fallback_backend_validation() {
+        let mut config = Config::default();
+        config.transcription.backend = "openai".to_string();
+        config.transcription.fallback_backend = Some("local".to_string());
+        assert!(config.validate_transcription().is_ok());
+
+        // The fallback loads the configured local model, so it is validated too
+        config.transcription.model = "invalid".to_string();
+        assert!(config.validate_transcription().is_err());
+
+        config.transcription.model = "base".to_string();
+        config.transcription.fallback_backend = Some("openai".to_string());
+        assert!(config.validate_transcription().is_err());
+
+        config.transcription.backend = "local".to_string();
+        config.transcription.fallback_backend = Some("local".to_string());
+        assert!(config.validate_transcription().is_err());
+    }
+
     #[test]
     fn test_valid_language_codes() {
         for lang in &["en", "es", "fr", "de", "it", "ja", "zh"] {
@@ -582,9 +1190,19 @@ mod tests {
 
     #[test]
     fn test_valid_injection_method() {
-        let mut config = Config::default();
-        config.injection.method = "dotool".to_string();
-        assert!(config.validate_injection().is_ok());
+        for method in &[
+            "auto",
+            "uinput",
+            "dotool",
+            "ydotool",
+            "wtype",
+            "xdotool",
+            "clipboard",
+        ] {
+            let mut config = Config::default();
+            config.injection.method = method.to_string();
+            assert!(config.validate_injection().is_ok());
+        }
     }
 
     #[test]
@@ -599,6 +1217,21 @@ mod tests {
             .contains("Invalid injection method"));
     }
 
+    #[test]
+    fn test_injection_layout_validation() {
+        for layout in &["us", "de(nodeadkeys)", "gb(extd)", "us(alt-intl)"] {
+            let mut config = Config::default();
+            config.injection.layout = (*layout).to_string();
+            assert!(config.validate_injection().is_ok(), "{layout}");
+        }
+
+        for layout in &["", "de()", "../us", "us(intl", "de(nodeadkeys)x"] {
+            let mut config = Config::default();
+            config.injection.layout = (*layout).to_string();
+            assert!(config.validate_injection().is_err(), "{layout}");
+        }
+    }
+
     #[test]
     fn test_injection_delay_ms_bounds() {
         let mut config = Config::default();
@@ -636,6 +1269,7 @@ mod tests {
         let toml_str = toml::to_string(&config).unwrap();
         assert!(toml_str.contains("[audio]"));
         assert!(toml_str.contains("[vad]"));
+        assert!(toml_str.contains("[recording]"));
         assert!(toml_str.contains("[transcription]"));
         assert!(toml_str.contains("[injection]"));
         assert!(toml_str.contains("[notifications]"));
@@ -707,6 +1341,7 @@ mod tests {
         assert_eq!(config.audio.device, None);
         assert_eq!(config.vad.aggressiveness, 3);
         assert_eq!(config.vad.silence_ms, 900);
+        assert_eq!(config.recording.mode, "manual");
         assert_eq!(config.transcription.backend, "openai");
         assert_eq!(config.transcription.model, "small");
         assert_eq!(config.transcription.language, "es");
@@ -18,7 +18,7 @@ pub enum ScribeError {
     #[error("IPC error: {0}\n\nTroubleshooting:\n- Is the daemon running? Start with: scribe\n- Check socket path: /tmp/scribe-$USER.sock\n- Try restarting the daemon")]
     Ipc(String),
 
-    #[error("Text injection error: {0}\n\nTroubleshooting:\n- Is dotool installed and in PATH?\n- Check uinput permissions: ls -l /dev/uinput\n- You may need to be in 'input' group or run setup script")]
+    #[error("Text injection error: {0}\n\nTroubleshooting:\n- Is the tool for injection.method installed and in PATH?\n- dotool/ydotool: check uinput permissions: ls -l /dev/uinput\n- You may need to be in 'input' group or run setup script\n- Without uinput access use method = \"wtype\", \"xdotool\" or \"clipboard\"")]
     Injection(String),
 
     #[error("{0}")]
@@ -43,11 +43,28 @@ pub enum TranscriptionError {
     #[error("API error: {0}\n\nTroubleshooting:\n- Check internet connection\n- Verify OpenAI service status\n- Try again in a moment or switch to local backend")]
     ApiError(String),
 
+    #[error("Server error: {0}\n\nTroubleshooting:\n- The transcription service failed on every retry\n- Check the service status and try again later\n- Set fallback_backend = \"local\" to transcribe locally when the API fails")]
+    ServerError(String),
+
     #[error("Network error: {0}\n\nTroubleshooting:\n- Check internet connection\n- Verify firewall settings\n- Try increasing api_timeout_secs in config\n- Consider using local backend for offline use")]
     NetworkError(String),
 
-    #[error("Model loading error: {0}\n\nTroubleshooting:\n- Ensure sufficient disk space in ~/.cache/huggingface/\n- Check internet connection for model download\n- Try a smaller model (tiny or base)\n- Verify model name in config (tiny/base/small/medium/large)")]
+    #[error("Model loading error: {0}\n\nTroubleshooting:\n- List installed models: scribe model list\n- Reinstall the model: scribe model download <name>\n- Ensure sufficient disk space in ~/.local/share/scribe/models/\n- Try a smaller model (tiny or base)\n- Verify transcription.model in config is listed by: scribe model list")]
     ModelError(String),
+
+    #[error("Transcription cancelled")]
+    Cancelled,
+}
+
+impl TranscriptionError {
+    /// Whether the request may succeed if retried later
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::QuotaExceeded | Self::NetworkError(_) | Self::ServerError(_)
+        )
+    }
 }
 
 pub type Result<T> = std::result::Result<T, ScribeError>;
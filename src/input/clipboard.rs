@@ -0,0 +1,106 @@
+use crate::error::{Result, ScribeError};
+use crate::input::inject::{Injector, Session};
+use crate::input::keys::KeyCombo;
+use crate::input::tools::{self, TypingTool};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Time for the clipboard owner to be announced before pasting
+const SETTLE_DELAY: Duration = Duration::from_millis(50);
+
+/// Text injector that copies text to the clipboard and presses Ctrl+V
+///
+/// Needs no uinput access on X11 (xclip + xdotool) or wlroots Wayland
+/// compositors (wl-copy + wtype). The pasted text stays in the clipboard.
+/// Other key presses go through the same tool as Ctrl+V.
+#[derive(Debug)]
+pub struct ClipboardInjector {
+    /// Copy command and its arguments; reads the text from stdin
+    copy: &'static [&'static str],
+    paste: TypingTool,
+}
+
+impl ClipboardInjector {
+    /// Create a clipboard injector for the tools of `session`
+    ///
+    /// # Errors
+    /// - Returns error if the session type is unknown
+    /// - Returns error if no copy or paste tool is installed
+    pub fn new(session: Session) -> Result<Self> {
+        let (copy, paste_tools): (&'static [&'static str], &[TypingTool]) = match session {
+            Session::Wayland => (&["wl-copy"], &[TypingTool::Wtype, TypingTool::Ydotool]),
+            Session::X11 => (
+                &["xclip", "-selection", "clipboard"],
+                &[TypingTool::Xdotool, TypingTool::Ydotool],
+            ),
+            Session::Unknown => {
+                return Err(ScribeError::Injection(
+                    "Clipboard injection needs a Wayland or X11 session (WAYLAND_DISPLAY or DISPLAY not set)"
+                        .to_string(),
+                ))
+            }
+        };
+        tools::require(copy[0])?;
+
+        let paste = paste_tools
+            .iter()
+            .copied()
+            .find(|tool| which::which(tool.program()).is_ok())
+            .ok_or_else(|| {
+                let names: Vec<_> = paste_tools.iter().map(|tool| tool.program()).collect();
+                ScribeError::Injection(format!(
+                    "No tool to press Ctrl+V found. Install one of: {names:?}"
+                ))
+            })?;
+
+        Ok(Self { copy, paste })
+    }
+
+    /// Put `text` on the clipboard
+    fn copy(&self, text: &str) -> Result<()> {
+        let program = self.copy[0];
+        let mut child = Command::new(program)
+            .args(&self.copy[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ScribeError::Injection(format!("Failed to run {program}: {e}")))?;
+
+        // Closing stdin lets the tool take ownership of the clipboard and return
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| ScribeError::Injection(format!("{program} stdin not available")))?
+            .write_all(text.as_bytes())
+            .map_err(|e| ScribeError::Injection(format!("Failed to write to {program}: {e}")))?;
+
+        let status = child
+            .wait()
+            .map_err(|e| ScribeError::Injection(format!("Failed to wait for {program}: {e}")))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(ScribeError::Injection(format!(
+                "{program} failed ({status})"
+            )))
+        }
+    }
+}
+
+impl Injector for ClipboardInjector {
+    fn name(&self) -> &'static str {
+        "clipboard"
+    }
+
+    fn inject(&mut self, text: &str) -> Result<()> {
+        self.copy(text)?;
+        std::thread::sleep(SETTLE_DELAY);
+        self.press(&KeyCombo::paste())
+    }
+
+    fn press(&mut self, combo: &KeyCombo) -> Result<()> {
+        tools::run(self.paste.program(), &self.paste.key_args(combo))
+    }
+}
@@ -1,242 +1,307 @@
+use crate::config::schema::InjectionConfig;
 use crate::error::{Result, ScribeError};
-use std::io::Write;
-use std::process::{Child, Command, Stdio};
+use crate::input::clipboard::ClipboardInjector;
+use crate::input::dotool::DotoolInjector;
+use crate::input::keys::{Key, KeyCombo};
+use crate::input::tools::{ToolInjector, TypingTool};
+use crate::input::uinput::UinputInjector;
+
+/// A way of typing text into the focused window
+pub trait Injector: Send + std::fmt::Debug {
+    /// Method name, as used in `injection.method`
+    fn name(&self) -> &'static str;
+
+    /// Type `text` into the focused window
+    ///
+    /// # Errors
+    /// - Returns error if the underlying tool fails
+    fn inject(&mut self, text: &str) -> Result<()>;
 
-/// Text injector using dotool
-///
-/// dotool is a command-line tool that sends keyboard/mouse events to the system.
-/// It reads commands from stdin, one per line:
-/// - `typedelay X` - Set delay between keystrokes (in ms)
-/// - `type TEXT` - Type the specified text
-///
-/// This struct maintains a long-lived dotool process for efficient text injection.
+    /// Press `combo` in the focused window
+    ///
+    /// # Errors
+    /// - Returns error if the underlying tool fails
+    fn press(&mut self, combo: &KeyCombo) -> Result<()>;
+
+    /// Release helper processes; called before shutdown
+    fn cleanup(&mut self) {}
+}
+
+/// Something to do in the focused window after a transcription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Type text
+    Type(String),
+    /// Press a key combination
+    Key(KeyCombo),
+    /// Erase the most recently typed text
+    DeleteLast,
+}
+
+/// Graphical session type, which decides the tools that can inject text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Session {
+    Wayland,
+    X11,
+    Unknown,
+}
+
+impl Session {
+    /// Detect the session type from the environment
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("XDG_SESSION_TYPE").ok().as_deref(),
+            std::env::var_os("WAYLAND_DISPLAY").is_some(),
+            std::env::var_os("DISPLAY").is_some(),
+        )
+    }
+
+    fn from_env(session_type: Option<&str>, wayland_display: bool, display: bool) -> Self {
+        match session_type {
+            Some("wayland") => Self::Wayland,
+            Some("x11") => Self::X11,
+            _ if wayland_display => Self::Wayland,
+            _ if display => Self::X11,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Text injector selected by `injection.method`
 #[derive(Debug)]
 pub struct TextInjector {
-    dotool_process: Option<Child>,
-    delay_ms: u64,
+    injector: Box<dyn Injector>,
+    /// Characters typed by the last `Type` action, erased by `DeleteLast`
+    last_typed: usize,
 }
 
 impl TextInjector {
-    /// Create a new text injector with specified typing delay
-    ///
-    /// This will spawn a dotool process and keep it alive for reuse.
-    /// The process is kept alive until `cleanup()` is called or the struct is dropped.
+    /// Create the injector configured by `method`, probing the session for `"auto"`
     ///
     /// # Errors
-    /// - Returns error if dotool binary not found in PATH
-    /// - Returns error if dotool process fails to start
-    pub fn new(delay_ms: u64) -> Result<Self> {
-        // Verify dotool is available
-        if which::which("dotool").is_err() {
-            return Err(ScribeError::Injection(
-                "dotool binary not found in PATH. Install with: cargo install dotool".to_string(),
-            ));
-        }
+    /// - Returns error if the method's tool is not installed
+    /// - Returns error if `"auto"` finds no usable tool
+    pub fn from_config(config: &InjectionConfig) -> Result<Self> {
+        let method = if config.method == "auto" {
+            let session = Session::detect();
+            let method = auto_method(session, uinput_writable(), |program| {
+                which::which(program).is_ok()
+            })?;
+            tracing::info!(?session, method, "Selected text injection method");
+            method
+        } else {
+            config.method.as_str()
+        };
+
+        let injector: Box<dyn Injector> = match method {
+            "uinput" => Box::new(UinputInjector::new(&config.layout, config.delay_ms)?),
+            "dotool" => Box::new(DotoolInjector::new(config.delay_ms)?),
+            "ydotool" => Box::new(ToolInjector::new(TypingTool::Ydotool, config.delay_ms)?),
+            "wtype" => Box::new(ToolInjector::new(TypingTool::Wtype, config.delay_ms)?),
+            "xdotool" => Box::new(ToolInjector::new(TypingTool::Xdotool, config.delay_ms)?),
+            "clipboard" => Box::new(ClipboardInjector::new(Session::detect())?),
+            other => {
+                return Err(ScribeError::Config(format!(
+                    "Unknown injection method: {other}"
+                )))
+            }
+        };
 
         Ok(Self {
-            dotool_process: None,
-            delay_ms,
+            injector,
+            last_typed: 0,
         })
     }
 
+    /// Name of the injection method in use
+    #[must_use]
+    pub fn method(&self) -> &'static str {
+        self.injector.name()
+    }
+
     /// Inject text into the active window
     ///
-    /// This sends the text to dotool for typing. The process is spawned on first use
-    /// and reused for subsequent calls for efficiency.
-    ///
     /// # Errors
-    /// - Returns error if dotool process fails to spawn
-    /// - Returns error if writing to dotool stdin fails
-    /// - Returns error if process unexpectedly terminates
+    /// - Returns error if the injection tool fails
     pub fn inject(&mut self, text: &str) -> Result<()> {
-        // Ensure process is running
-        self.ensure_process_running()?;
-
-        let process = self
-            .dotool_process
-            .as_mut()
-            .ok_or_else(|| ScribeError::Injection("dotool process not available".to_string()))?;
-
-        let stdin = process
-            .stdin
-            .as_mut()
-            .ok_or_else(|| ScribeError::Injection("dotool stdin not available".to_string()))?;
-
-        // Set typing delay
-        writeln!(stdin, "typedelay {}", self.delay_ms).map_err(|e| {
-            ScribeError::Injection(format!("Failed to write typedelay command: {e}"))
-        })?;
-
-        // Send text - dotool expects "type TEXT" where TEXT is the literal string
-        writeln!(stdin, "type {text}")
-            .map_err(|e| ScribeError::Injection(format!("Failed to write type command: {e}")))?;
-
-        // Flush to ensure commands are sent immediately
-        stdin
-            .flush()
-            .map_err(|e| ScribeError::Injection(format!("Failed to flush stdin: {e}")))?;
-
+        self.injector.inject(text)?;
+        self.last_typed = text
+            .chars()
+            .filter(|c| !c.is_control() || *c == '\n')
+            .count();
         Ok(())
     }
 
-    /// Clean up the dotool process
+    /// Perform `actions` in order
     ///
-    /// This kills the dotool process and cleans up resources.
-    /// Called automatically on drop, but can be called manually for explicit cleanup.
-    pub fn cleanup(&mut self) {
-        if let Some(mut process) = self.dotool_process.take() {
-            let _ = process.kill();
-            let _ = process.wait();
-        }
-    }
-
-    /// Ensure the dotool process is running, spawning it if necessary
-    fn ensure_process_running(&mut self) -> Result<()> {
-        // Check if process is still alive
-        if let Some(process) = &mut self.dotool_process {
-            if let Ok(Some(_)) = process.try_wait() {
-                // Process exited, need to restart
-                self.dotool_process = None;
+    /// # Errors
+    /// - Returns error if the injection tool fails
+    pub fn perform(&mut self, actions: &[Action]) -> Result<()> {
+        for action in actions {
+            match action {
+                Action::Type(text) => self.inject(text)?,
+                Action::Key(combo) => self.injector.press(combo)?,
+                Action::DeleteLast => {
+                    let backspace = KeyCombo::key(Key::BackSpace);
+                    for _ in 0..std::mem::take(&mut self.last_typed) {
+                        self.injector.press(&backspace)?;
+                    }
+                }
             }
         }
-
-        // Spawn new process if needed
-        if self.dotool_process.is_none() {
-            let process = Command::new("dotool")
-                .stdin(Stdio::piped())
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
-                .map_err(|e| ScribeError::Injection(format!("Failed to spawn dotool: {e}")))?;
-
-            self.dotool_process = Some(process);
-        }
-
         Ok(())
     }
-}
 
-impl Drop for TextInjector {
-    fn drop(&mut self) {
-        self.cleanup();
+    /// Clean up helper processes
+    pub fn cleanup(&mut self) {
+        self.injector.cleanup();
     }
 }
 
+/// Whether the current user may create virtual input devices
+fn uinput_writable() -> bool {
+    nix::unistd::access("/dev/uinput", nix::unistd::AccessFlags::W_OK).is_ok()
+}
+
+/// First injection method for `session` whose tool is installed
+///
+/// The built-in uinput injector needs no binary but, like dotool, write
+/// access to `/dev/uinput`; ydotool relies on the ydotoold daemon instead.
+/// dotool is only reached when uinput is unusable, so it is not a candidate.
+fn auto_method(
+    session: Session,
+    uinput: bool,
+    installed: impl Fn(&str) -> bool,
+) -> Result<&'static str> {
+    let candidates: &[&'static str] = match session {
+        Session::Wayland => &["uinput", "ydotool", "wtype"],
+        Session::X11 => &["xdotool", "uinput", "ydotool"],
+        Session::Unknown => &["uinput", "ydotool"],
+    };
+
+    candidates
+        .iter()
+        .copied()
+        .find(|&method| {
+            if method == "uinput" {
+                uinput
+            } else {
+                installed(method)
+            }
+        })
+        .ok_or_else(|| {
+            ScribeError::Injection(format!(
+                "No text injection tool found for {session:?} session (tried {candidates:?}; uinput needs write access to /dev/uinput)"
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_new_without_dotool() {
-        // This test verifies error handling when dotool is not available
-        // In CI, dotool won't be installed, so we expect an error
-        let result = TextInjector::new(2);
-
-        // If dotool is installed, test passes
-        // If not installed, verify we get the right error
-        if let Err(err) = result {
-            assert!(matches!(err, ScribeError::Injection(_)));
-            assert!(err.to_string().contains("dotool binary not found"));
-        }
+    fn test_session_detection() {
+        assert_eq!(
+            Session::from_env(Some("wayland"), false, true),
+            Session::Wayland
+        );
+        assert_eq!(Session::from_env(Some("x11"), true, true), Session::X11);
+        assert_eq!(Session::from_env(Some("tty"), true, true), Session::Wayland);
+        assert_eq!(Session::from_env(None, false, true), Session::X11);
+        assert_eq!(Session::from_env(None, false, false), Session::Unknown);
     }
 
     #[test]
-    fn test_new_with_delay() {
-        // Test that we can create an injector with various delays
-        let delays = [0, 2, 5, 10, 50];
-
-        for delay in delays {
-            let injector = TextInjector::new(delay);
-            // If dotool available, should succeed
-            // If not, should fail with expected error
-            if let Ok(inj) = injector {
-                assert_eq!(inj.delay_ms, delay);
-            }
-        }
-    }
+    fn test_auto_method() {
+        let all = |_: &str| true;
+        assert_eq!(auto_method(Session::Wayland, true, all).unwrap(), "uinput");
+        assert_eq!(auto_method(Session::X11, true, all).unwrap(), "xdotool");
+        assert_eq!(
+            auto_method(Session::Unknown, true, |_| false).unwrap(),
+            "uinput"
+        );
 
-    #[test]
-    fn test_cleanup() {
-        // Test that cleanup doesn't panic even when no process exists
-        let mut injector = TextInjector {
-            dotool_process: None,
-            delay_ms: 2,
-        };
+        // Without uinput access the built-in injector is skipped
+        assert_eq!(
+            auto_method(Session::Wayland, false, all).unwrap(),
+            "ydotool"
+        );
+        let wtype_only = |program: &str| program == "wtype";
+        assert_eq!(
+            auto_method(Session::Wayland, false, wtype_only).unwrap(),
+            "wtype"
+        );
 
-        injector.cleanup();
-        // Should not panic
+        // wtype cannot type outside Wayland
+        assert!(auto_method(Session::X11, false, wtype_only).is_err());
+        assert!(auto_method(Session::Unknown, false, |_| false).is_err());
     }
 
-    // Integration test - only runs if dotool is available
-    #[test]
-    #[ignore = "requires dotool binary to be installed"]
-    fn test_inject_text() {
-        let mut injector = TextInjector::new(2).expect("dotool must be installed for this test");
-
-        // This would actually type text, so we skip in normal tests
-        // In a real integration test, you'd verify the text appears
-        let result = injector.inject("Hello, World!");
-
-        assert!(
-            result.is_ok(),
-            "Failed to inject text: {}",
-            result.unwrap_err()
-        );
+    /// Records what it is asked to do
+    #[derive(Debug, Default)]
+    struct Recorder {
+        events: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
     }
 
-    #[test]
-    #[ignore = "requires dotool binary to be installed"]
-    fn test_inject_multiline() {
-        let mut injector = TextInjector::new(2).expect("dotool must be installed for this test");
+    impl Injector for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
 
-        let text = "Line 1\nLine 2\nLine 3";
-        let result = injector.inject(text);
+        fn inject(&mut self, text: &str) -> Result<()> {
+            self.events.lock().unwrap().push(format!("type {text}"));
+            Ok(())
+        }
 
-        assert!(
-            result.is_ok(),
-            "Failed to inject multiline text: {}",
-            result.unwrap_err()
-        );
+        fn press(&mut self, combo: &KeyCombo) -> Result<()> {
+            self.events.lock().unwrap().push(format!("key {combo}"));
+            Ok(())
+        }
     }
 
     #[test]
-    #[ignore = "requires dotool binary to be installed"]
-    fn test_inject_special_chars() {
-        let mut injector = TextInjector::new(2).expect("dotool must be installed for this test");
-
-        // Test with special characters
-        let texts = vec![
-            "Hello, World!",
-            "Test with \"quotes\"",
-            "Symbols: @#$%^&*()",
-            "Unicode: café, naïve, 日本語",
-        ];
-
-        for text in texts {
-            let result = injector.inject(text);
-            assert!(
-                result.is_ok(),
-                "Failed to inject '{}': {}",
-                text,
-                result.unwrap_err()
-            );
-        }
+    fn test_perform() {
+        let recorder = Recorder::default();
+        let events = std::sync::Arc::clone(&recorder.events);
+        let mut injector = TextInjector {
+            injector: Box::new(recorder),
+            last_typed: 0,
+        };
+
+        injector
+            .perform(&[
+                Action::Type("one\n".to_string()),
+                Action::Type("two ".to_string()),
+                Action::DeleteLast,
+                Action::DeleteLast,
+                Action::Key("ctrl+a".parse().unwrap()),
+            ])
+            .unwrap();
+
+        // Only the last typed text is erased, once
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "type one\n",
+                "type two ",
+                "key backspace",
+                "key backspace",
+                "key backspace",
+                "key backspace",
+                "key ctrl+a"
+            ]
+        );
     }
 
     #[test]
-    #[ignore = "requires dotool binary to be installed"]
-    fn test_multiple_injections() {
-        let mut injector = TextInjector::new(2).expect("dotool must be installed for this test");
-
-        // Test that we can reuse the same process for multiple injections
-        for i in 1..=5 {
-            let result = injector.inject(&format!("Injection {i} "));
-            assert!(
-                result.is_ok(),
-                "Failed injection {}: {}",
-                i,
-                result.unwrap_err()
-            );
-        }
+    fn test_unknown_method() {
+        let config = InjectionConfig {
+            method: "telepathy".to_string(),
+            delay_ms: 2,
+            layout: "us".to_string(),
+        };
+        assert!(TextInjector::from_config(&config).is_err());
     }
 }
@@ -0,0 +1,642 @@
+//! Character to keycode mapping read from XKB symbols files
+//!
+//! Only the parts of the XKB format needed to type text are understood:
+//! `xkb_symbols` blocks, `include` statements and the symbols of the
+//! alphanumeric keys in the first group. Levels 1-4 map to no modifier,
+//! Shift, `AltGr` and Shift+`AltGr`.
+
+use crate::error::{Result, ScribeError};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Linux input event codes used besides the layout's own keys
+pub(crate) const KEY_TAB: u16 = 15;
+pub(crate) const KEY_ENTER: u16 = 28;
+pub(crate) const KEY_SPACE: u16 = 57;
+
+/// Deepest include chain followed before giving up
+const MAX_INCLUDE_DEPTH: usize = 10;
+
+/// One key press, with the modifiers held while pressing it
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyStroke {
+    /// Linux input event code
+    pub code: u16,
+    pub shift: bool,
+    pub altgr: bool,
+    pub ctrl: bool,
+}
+
+impl KeyStroke {
+    const fn plain(code: u16) -> Self {
+        Self {
+            code,
+            shift: false,
+            altgr: false,
+            ctrl: false,
+        }
+    }
+}
+
+/// Characters of a keyboard layout and the strokes that type them
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    keys: HashMap<char, KeyStroke>,
+}
+
+impl Keymap {
+    /// Load `layout` ("us", "de(nodeadkeys)", ...) from the XKB symbols directory
+    ///
+    /// The directory is `$XKB_CONFIG_ROOT/symbols`, or `/usr/share/X11/xkb/symbols`.
+    ///
+    /// # Errors
+    /// - Returns error if the layout or variant is not found
+    pub fn load(layout: &str) -> Result<Self> {
+        let dir = std::env::var_os("XKB_CONFIG_ROOT")
+            .map_or_else(|| PathBuf::from("/usr/share/X11/xkb"), PathBuf::from)
+            .join("symbols");
+
+        Self::parse(layout, |file| std::fs::read_to_string(dir.join(file)).ok())
+            .map_err(|e| ScribeError::Injection(format!("{e} (XKB symbols in {})", dir.display())))
+    }
+
+    /// Build the keymap of `layout`, reading symbols files through `read`
+    fn parse(layout: &str, read: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let (file, variant) = split_spec(layout);
+        let mut levels = BTreeMap::new();
+        collect_symbols(file, variant, &read, &mut levels, 0)?;
+
+        let mut keys = HashMap::from([
+            (' ', KeyStroke::plain(KEY_SPACE)),
+            ('\n', KeyStroke::plain(KEY_ENTER)),
+            ('\t', KeyStroke::plain(KEY_TAB)),
+        ]);
+
+        // Prefer the stroke with the fewest modifiers for every character
+        for level in 0..4 {
+            for (&code, symbols) in &levels {
+                let Some(c) = symbols
+                    .get(level)
+                    .and_then(Option::as_deref)
+                    .and_then(keysym_char)
+                else {
+                    continue;
+                };
+                keys.entry(c).or_insert(KeyStroke {
+                    code,
+                    shift: level % 2 == 1,
+                    altgr: level >= 2,
+                    ctrl: false,
+                });
+            }
+        }
+
+        Ok(Self { keys })
+    }
+
+    /// Stroke that types `c`, if the layout has it
+    #[must_use]
+    pub fn get(&self, c: char) -> Option<KeyStroke> {
+        self.keys.get(&c).copied()
+    }
+
+    /// Strokes that type `text`
+    ///
+    /// Characters missing from the layout are entered as Ctrl+Shift+U, their
+    /// hex code point and Space, which GTK and `IBus` turn into the character.
+    ///
+    /// # Errors
+    /// - Returns error if a Unicode sequence needs a key the layout lacks
+    pub fn strokes(&self, text: &str) -> Result<Vec<KeyStroke>> {
+        let mut strokes = Vec::with_capacity(text.len());
+        for c in text.chars() {
+            if let Some(stroke) = self.get(c) {
+                strokes.push(stroke);
+            } else if !c.is_control() {
+                self.unicode_input(c, &mut strokes)?;
+            }
+        }
+        Ok(strokes)
+    }
+
+    fn unicode_input(&self, c: char, strokes: &mut Vec<KeyStroke>) -> Result<()> {
+        let key = |k: char| {
+            self.get(k).ok_or_else(|| {
+                ScribeError::Injection(format!(
+                    "Cannot type '{c}': the layout has no key for '{k}' to enter it as Unicode"
+                ))
+            })
+        };
+
+        let u = key('u')?;
+        strokes.push(KeyStroke {
+            shift: true,
+            ctrl: true,
+            ..u
+        });
+        for digit in format!("{:x}", u32::from(c)).chars() {
+            strokes.push(key(digit)?);
+        }
+        strokes.push(KeyStroke::plain(KEY_SPACE));
+        Ok(())
+    }
+}
+
+/// Split "file(variant)" into its parts
+fn split_spec(spec: &str) -> (&str, Option<&str>) {
+    spec.strip_suffix(')')
+        .and_then(|spec| spec.split_once('('))
+        .map_or((spec, None), |(file, variant)| (file, Some(variant)))
+}
+
+/// Merge the key symbols of `file(variant)` and its includes into `levels`
+fn collect_symbols(
+    file: &str,
+    variant: Option<&str>,
+    read: &impl Fn(&str) -> Option<String>,
+    levels: &mut BTreeMap<u16, Vec<Option<String>>>,
+    depth: usize,
+) -> Result<()> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(ScribeError::Injection(format!(
+            "XKB include chain too deep at '{file}'"
+        )));
+    }
+
+    let contents = read(file)
+        .ok_or_else(|| ScribeError::Injection(format!("XKB layout '{file}' not found")))?;
+    let contents = strip_comments(&contents);
+    let body = find_block(&contents, variant).ok_or_else(|| {
+        ScribeError::Injection(format!(
+            "XKB layout '{file}' has no variant '{}'",
+            variant.unwrap_or("default")
+        ))
+    })?;
+
+    for mut statement in body.split(';') {
+        // Includes are not terminated by ';' and may precede a statement
+        while let Some((spec, rest)) = include_spec(statement) {
+            // "pc+us(basic)|inet(evdev)"; parts for other groups carry ":<n>"
+            for part in spec.split(['+', '|']).filter(|part| !part.is_empty()) {
+                if part.contains(':') {
+                    continue;
+                }
+                let (file, variant) = split_spec(part);
+                collect_symbols(file, variant, read, levels, depth + 1)?;
+            }
+            statement = rest;
+        }
+
+        if let Some((code, symbols)) = parse_key(statement.trim()) {
+            let entry = levels.entry(code).or_default();
+            if entry.len() < symbols.len() {
+                entry.resize(symbols.len(), None);
+            }
+            for (level, symbol) in symbols.into_iter().enumerate() {
+                if symbol.is_some() {
+                    entry[level] = symbol;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn strip_comments(contents: &str) -> String {
+    contents
+        .lines()
+        .map(|line| line.split_once("//").map_or(line, |(code, _)| code))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Body of the `xkb_symbols` block named `variant`, or of the default block
+///
+/// Without a block marked `default`, the first block is the default.
+fn find_block<'a>(contents: &'a str, variant: Option<&str>) -> Option<&'a str> {
+    let mut first = None;
+    let mut block_start = 0;
+
+    while let Some(offset) = contents[block_start..].find("xkb_symbols") {
+        let header = block_start + offset;
+        let flags = &contents[block_start..header];
+        let open = header + contents[header..].find('{')?;
+        let name = contents[header + "xkb_symbols".len()..open]
+            .trim()
+            .trim_matches('"');
+
+        let mut depth = 0;
+        let close = contents[open..].char_indices().find_map(|(i, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(open + i)
+        })?;
+        let body = &contents[open + 1..close];
+
+        let matches = variant.map_or_else(
+            || flags.split_whitespace().any(|flag| flag == "default"),
+            |variant| name == variant,
+        );
+        if matches {
+            return Some(body);
+        }
+        first.get_or_insert(body);
+        block_start = close + 1;
+    }
+
+    variant.map_or(first, |_| None)
+}
+
+/// Spec of a leading `include`-like statement, and the text after it
+fn include_spec(statement: &str) -> Option<(&str, &str)> {
+    let (keyword, rest) = statement.trim_start().split_once(char::is_whitespace)?;
+    if !matches!(keyword, "include" | "augment" | "override" | "replace") {
+        return None;
+    }
+    let (spec, rest) = rest.trim_start().strip_prefix('"')?.split_once('"')?;
+    Some((spec, rest))
+}
+
+/// Keycode and level symbols of a `key <NAME> { ... }` statement
+///
+/// `NoSymbol` levels are `None` so they keep the included definition.
+fn parse_key(statement: &str) -> Option<(u16, Vec<Option<String>>)> {
+    let rest = statement.strip_prefix("key")?.trim_start();
+    let name = rest.strip_prefix('<')?.split('>').next()?;
+    let code = key_code(name)?;
+
+    // Skip "[Group1]" subscripts to reach the symbol list
+    let mut rest = rest;
+    let symbols = loop {
+        let open = rest.find('[')?;
+        let close = open + rest[open..].find(']')?;
+        let inner = rest[open + 1..close].trim();
+        rest = &rest[close + 1..];
+        if !inner.starts_with("Group") {
+            break inner;
+        }
+    };
+
+    let symbols = symbols
+        .split(',')
+        .map(str::trim)
+        .map(|symbol| (symbol != "NoSymbol" && !symbol.is_empty()).then(|| symbol.to_string()))
+        .collect();
+    Some((code, symbols))
+}
+
+/// Linux input event code of an XKB key name (evdev keycode minus 8)
+fn key_code(name: &str) -> Option<u16> {
+    let row = |first: u16, keys: u16| {
+        name.get(2..)?
+            .parse::<u16>()
+            .ok()
+            .filter(|n| (1..=keys).contains(n))
+            .map(|n| first + n - 1)
+    };
+
+    match name {
+        "TLDE" => Some(41),
+        "BKSL" | "AC12" => Some(43),
+        "LSGT" => Some(86),
+        "AB11" => Some(89),
+        "AE13" => Some(124),
+        "SPCE" => Some(KEY_SPACE),
+        _ if name.starts_with("AE") => row(2, 12),
+        _ if name.starts_with("AD") => row(16, 12),
+        _ if name.starts_with("AC") => row(30, 11),
+        _ if name.starts_with("AB") => row(44, 10),
+        _ => None,
+    }
+}
+
+/// Keysym names of U+00A0 to U+00FF, in order
+const LATIN1: [&str; 96] = [
+    "nobreakspace",
+    "exclamdown",
+    "cent",
+    "sterling",
+    "currency",
+    "yen",
+    "brokenbar",
+    "section",
+    "diaeresis",
+    "copyright",
+    "ordfeminine",
+    "guillemotleft",
+    "notsign",
+    "hyphen",
+    "registered",
+    "macron",
+    "degree",
+    "plusminus",
+    "twosuperior",
+    "threesuperior",
+    "acute",
+    "mu",
+    "paragraph",
+    "periodcentered",
+    "cedilla",
+    "onesuperior",
+    "masculine",
+    "guillemotright",
+    "onequarter",
+    "onehalf",
+    "threequarters",
+    "questiondown",
+    "Agrave",
+    "Aacute",
+    "Acircumflex",
+    "Atilde",
+    "Adiaeresis",
+    "Aring",
+    "AE",
+    "Ccedilla",
+    "Egrave",
+    "Eacute",
+    "Ecircumflex",
+    "Ediaeresis",
+    "Igrave",
+    "Iacute",
+    "Icircumflex",
+    "Idiaeresis",
+    "ETH",
+    "Ntilde",
+    "Ograve",
+    "Oacute",
+    "Ocircumflex",
+    "Otilde",
+    "Odiaeresis",
+    "multiply",
+    "Oslash",
+    "Ugrave",
+    "Uacute",
+    "Ucircumflex",
+    "Udiaeresis",
+    "Yacute",
+    "THORN",
+    "ssharp",
+    "agrave",
+    "aacute",
+    "acircumflex",
+    "atilde",
+    "adiaeresis",
+    "aring",
+    "ae",
+    "ccedilla",
+    "egrave",
+    "eacute",
+    "ecircumflex",
+    "ediaeresis",
+    "igrave",
+    "iacute",
+    "icircumflex",
+    "idiaeresis",
+    "eth",
+    "ntilde",
+    "ograve",
+    "oacute",
+    "ocircumflex",
+    "otilde",
+    "odiaeresis",
+    "division",
+    "oslash",
+    "ugrave",
+    "uacute",
+    "ucircumflex",
+    "udiaeresis",
+    "yacute",
+    "thorn",
+    "ydiaeresis",
+];
+
+/// ASCII punctuation and common symbols outside Latin-1
+const NAMED: &[(&str, char)] = &[
+    ("space", ' '),
+    ("exclam", '!'),
+    ("quotedbl", '"'),
+    ("numbersign", '#'),
+    ("dollar", '$'),
+    ("percent", '%'),
+    ("ampersand", '&'),
+    ("apostrophe", '\''),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("asterisk", '*'),
+    ("plus", '+'),
+    ("comma", ','),
+    ("minus", '-'),
+    ("period", '.'),
+    ("slash", '/'),
+    ("colon", ':'),
+    ("semicolon", ';'),
+    ("less", '<'),
+    ("equal", '='),
+    ("greater", '>'),
+    ("question", '?'),
+    ("at", '@'),
+    ("bracketleft", '['),
+    ("backslash", '\\'),
+    ("bracketright", ']'),
+    ("asciicircum", '^'),
+    ("underscore", '_'),
+    ("grave", '`'),
+    ("braceleft", '{'),
+    ("bar", '|'),
+    ("braceright", '}'),
+    ("asciitilde", '~'),
+    ("guillemetleft", '«'),
+    ("guillemetright", '»'),
+    ("ordmasculine", 'º'),
+    ("Eth", 'Ð'),
+    ("Thorn", 'Þ'),
+    ("Ooblique", 'Ø'),
+    ("ooblique", 'ø'),
+    ("EuroSign", '€'),
+    ("OE", 'Œ'),
+    ("oe", 'œ'),
+    ("endash", '–'),
+    ("emdash", '—'),
+    ("ellipsis", '…'),
+    ("leftsinglequotemark", '‘'),
+    ("rightsinglequotemark", '’'),
+    ("singlelowquotemark", '‚'),
+    ("leftdoublequotemark", '“'),
+    ("rightdoublequotemark", '”'),
+    ("doublelowquotemark", '„'),
+];
+
+/// Character produced by a keysym name; dead keys and functions have none
+fn keysym_char(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.is_ascii_alphanumeric().then_some(c);
+    }
+
+    if let Some(hex) = name.strip_prefix('U') {
+        if let Ok(code) = u32::from_str_radix(hex, 16) {
+            return char::from_u32(code);
+        }
+    }
+    if let Some(hex) = name.strip_prefix("0x") {
+        // Keysyms 0x01000000 + code point are Unicode characters
+        let keysym = u32::from_str_radix(hex, 16).ok()?;
+        return keysym
+            .checked_sub(0x0100_0000)
+            .filter(|&code| code > 0x7f)
+            .and_then(char::from_u32);
+    }
+
+    LATIN1
+        .iter()
+        .zip(0xa0..)
+        .find_map(|(&latin, code)| (latin == name).then(|| char::from_u32(code)).flatten())
+        .or_else(|| {
+            NAMED
+                .iter()
+                .find(|(named, _)| *named == name)
+                .map(|&(_, c)| c)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LATIN: &str = r#"
+        // Shared by several layouts
+        default partial alphanumeric_keys
+        xkb_symbols "basic" {
+            key <AE01> { [ 1, exclam, onesuperior, exclamdown ] };
+            key <AE02> { [ 2, at, twosuperior, oneeighth ] };
+            key <AD01> { [ q, Q, at, Greek_OMEGA ] };
+            key <AD03> { [ e, E, EuroSign, cent ] };
+            key <AD07> { [ u, U, downarrow, uparrow ] };
+            key <AC01> { [ a, A, ae, AE ] };
+            key <AB03> { [ c, C, cent, copyright ] };
+            key <AB04> { [ v, V, leftdoublequotemark, leftsinglequotemark ] };
+            key <AE10> { [ 0, parenright ] };
+        };
+    "#;
+
+    const DE: &str = r#"
+        default
+        xkb_symbols "basic" {
+            include "latin(basic)"
+            name[Group1]="German";
+            key <AE02> { [ 2, quotedbl, twosuperior, oneeighth ] };
+            key <AE11> {type[Group1]="FOUR_LEVEL_PLUS_LOCK", symbols[Group1]=
+                          [ssharp, question, backslash, questiondown, 0x1001E9E ]};
+            key <AC11> { [ adiaeresis, Adiaeresis, dead_circumflex, dead_caron ] };
+            key <AD03> { [ NoSymbol, NoSymbol, U20AC ] };
+            include "level3(ralt_switch)"
+        };
+
+        xkb_symbols "nodeadkeys" {
+            include "de(basic)"
+            key <AC11> { [ adiaeresis, Adiaeresis, asciicircum, caron ] };
+        };
+    "#;
+
+    const LEVEL3: &str = r#"
+        xkb_symbols "ralt_switch" {
+            key <RALT> { type[Group1]="ONE_LEVEL", symbols[Group1] = [ ISO_Level3_Shift ] };
+        };
+    "#;
+
+    fn read(file: &str) -> Option<String> {
+        match file {
+            "latin" => Some(LATIN.to_string()),
+            "de" => Some(DE.to_string()),
+            "level3" => Some(LEVEL3.to_string()),
+            _ => None,
+        }
+    }
+
+    fn stroke(code: u16, shift: bool, altgr: bool) -> KeyStroke {
+        KeyStroke {
+            code,
+            shift,
+            altgr,
+            ctrl: false,
+        }
+    }
+
+    #[test]
+    fn test_keysym_char() {
+        assert_eq!(keysym_char("a"), Some('a'));
+        assert_eq!(keysym_char("7"), Some('7'));
+        assert_eq!(keysym_char("exclam"), Some('!'));
+        assert_eq!(keysym_char("adiaeresis"), Some('ä'));
+        assert_eq!(keysym_char("nobreakspace"), Some('\u{a0}'));
+        assert_eq!(keysym_char("ydiaeresis"), Some('ÿ'));
+        assert_eq!(keysym_char("U20AC"), Some('€'));
+        assert_eq!(keysym_char("0x1001E9E"), Some('ẞ'));
+        assert_eq!(keysym_char("dead_acute"), None);
+        assert_eq!(keysym_char("ISO_Level3_Shift"), None);
+    }
+
+    #[test]
+    fn test_split_spec() {
+        assert_eq!(split_spec("us"), ("us", None));
+        assert_eq!(split_spec("de(nodeadkeys)"), ("de", Some("nodeadkeys")));
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        let keymap = Keymap::parse("de", read).unwrap();
+
+        assert_eq!(keymap.get('1'), Some(stroke(2, false, false)));
+        assert_eq!(keymap.get('!'), Some(stroke(2, true, false)));
+        // Overridden by the layout, not the included file
+        assert_eq!(keymap.get('"'), Some(stroke(3, true, false)));
+        assert_eq!(keymap.get('ß'), Some(stroke(12, false, false)));
+        assert_eq!(keymap.get('\\'), Some(stroke(12, false, true)));
+        assert_eq!(keymap.get('ä'), Some(stroke(40, false, false)));
+        // NoSymbol keeps the included levels
+        assert_eq!(keymap.get('E'), Some(stroke(18, true, false)));
+        assert_eq!(keymap.get('€'), Some(stroke(18, false, true)));
+        // '@' is on level 2 of AE02 in latin, but the layout replaced that
+        assert_eq!(keymap.get('@'), Some(stroke(16, false, true)));
+        assert_eq!(keymap.get('\n'), Some(stroke(KEY_ENTER, false, false)));
+        assert_eq!(keymap.get('^'), None);
+
+        let keymap = Keymap::parse("de(nodeadkeys)", read).unwrap();
+        assert_eq!(keymap.get('^'), Some(stroke(40, false, true)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Keymap::parse("xx", read).is_err());
+        assert!(Keymap::parse("de(missing)", read).is_err());
+    }
+
+    #[test]
+    fn test_strokes_unicode_fallback() {
+        let keymap = Keymap::parse("de", read).unwrap();
+
+        let strokes = keymap.strokes("a€").unwrap();
+        assert_eq!(strokes[0], stroke(30, false, false));
+        assert_eq!(strokes[1], stroke(18, false, true));
+
+        // '‐' is U+2010: Ctrl+Shift+U, 2, 0, 1, 0, Space
+        let strokes = keymap.strokes("‐").unwrap();
+        assert_eq!(strokes.len(), 6);
+        assert!(strokes[0].ctrl && strokes[0].shift);
+        assert_eq!(strokes[0].code, 22);
+        assert_eq!(strokes[1], stroke(3, false, false));
+        assert_eq!(strokes[2], stroke(11, false, false));
+        assert_eq!(strokes[5], stroke(KEY_SPACE, false, false));
+
+        // Control characters without a key are dropped
+        assert_eq!(keymap.strokes("\u{7}").unwrap(), Vec::new());
+
+        // The hex digit 9 is missing from the test layout
+        assert!(keymap.strokes("♪").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Modifier held while pressing a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    /// Linux input event code of the left-hand modifier key
+    #[must_use]
+    pub const fn code(self) -> u16 {
+        match self {
+            Self::Ctrl => 29,
+            Self::Shift => 42,
+            Self::Alt => 56,
+            Self::Super => 125,
+        }
+    }
+
+    /// Name used by dotool and xdotool
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ctrl => "ctrl",
+            Self::Shift => "shift",
+            Self::Alt => "alt",
+            Self::Super => "super",
+        }
+    }
+}
+
+/// Key that can be pressed by name, independent of the injection method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Letter or digit key; letters are lowercase
+    Char(char),
+    Enter,
+    Tab,
+    Escape,
+    BackSpace,
+    Delete,
+    Space,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+/// Names accepted in config and used by dotool, with their X keysym names
+const KEY_NAMES: &[(Key, &str, &str)] = &[
+    (Key::Enter, "enter", "Return"),
+    (Key::Tab, "tab", "Tab"),
+    (Key::Escape, "esc", "Escape"),
+    (Key::BackSpace, "backspace", "BackSpace"),
+    (Key::Delete, "delete", "Delete"),
+    (Key::Space, "space", "space"),
+    (Key::Left, "left", "Left"),
+    (Key::Right, "right", "Right"),
+    (Key::Up, "up", "Up"),
+    (Key::Down, "down", "Down"),
+    (Key::Home, "home", "Home"),
+    (Key::End, "end", "End"),
+    (Key::PageUp, "pageup", "Prior"),
+    (Key::PageDown, "pagedown", "Next"),
+];
+
+/// Letters and digits in the order of their US QWERTY event codes
+const CHAR_ROWS: &[(u16, &str)] = &[
+    (2, "1234567890"),
+    (16, "qwertyuiop"),
+    (30, "asdfghjkl"),
+    (44, "zxcvbnm"),
+];
+
+impl Key {
+    /// Linux input event code; characters use their US QWERTY position
+    #[must_use]
+    pub fn code(self) -> u16 {
+        match self {
+            Self::Char(c) => CHAR_ROWS
+                .iter()
+                .find_map(|&(first, row)| {
+                    row.chars()
+                        .position(|key| key == c)
+                        .and_then(|i| u16::try_from(i).ok())
+                        .map(|i| first + i)
+                })
+                .unwrap_or(0),
+            Self::Enter => 28,
+            Self::Tab => 15,
+            Self::Escape => 1,
+            Self::BackSpace => 14,
+            Self::Delete => 111,
+            Self::Space => 57,
+            Self::Left => 105,
+            Self::Right => 106,
+            Self::Up => 103,
+            Self::Down => 108,
+            Self::Home => 102,
+            Self::End => 107,
+            Self::PageUp => 104,
+            Self::PageDown => 109,
+        }
+    }
+
+    /// X keysym name, used by xdotool and wtype
+    #[must_use]
+    pub fn keysym(self) -> String {
+        self.names()
+            .map_or_else(|| self.to_string(), |(_, keysym)| keysym.to_string())
+    }
+
+    fn names(self) -> Option<(&'static str, &'static str)> {
+        KEY_NAMES
+            .iter()
+            .find(|(key, _, _)| *key == self)
+            .map(|&(_, name, keysym)| (name, keysym))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.names()) {
+            (Self::Char(c), _) => write!(f, "{c}"),
+            (_, Some((name, _))) => f.write_str(name),
+            (_, None) => write!(f, "{self:?}"),
+        }
+    }
+}
+
+/// Key pressed together with modifiers, written like "ctrl+shift+t"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub modifiers: Vec<Modifier>,
+    pub key: Key,
+}
+
+impl KeyCombo {
+    /// A key without modifiers
+    #[must_use]
+    pub const fn key(key: Key) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key,
+        }
+    }
+
+    /// Ctrl+V
+    #[must_use]
+    pub fn paste() -> Self {
+        Self {
+            modifiers: vec![Modifier::Ctrl],
+            key: Key::Char('v'),
+        }
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let mut parts: Vec<&str> = lower.split('+').map(str::trim).collect();
+        let key = parts.pop().unwrap_or_default();
+
+        let modifiers = parts
+            .into_iter()
+            .map(|modifier| match modifier {
+                "ctrl" | "control" => Ok(Modifier::Ctrl),
+                "shift" => Ok(Modifier::Shift),
+                "alt" => Ok(Modifier::Alt),
+                "super" | "meta" | "logo" => Ok(Modifier::Super),
+                other => Err(format!("unknown modifier '{other}' in '{s}'")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let names: Vec<_> = KEY_NAMES.iter().map(|(_, name, _)| *name).collect();
+        let key = parse_key(key).ok_or_else(|| {
+            format!("unknown key '{key}' in '{s}'. Use a letter, a digit or one of: {names:?}")
+        })?;
+
+        Ok(Self { modifiers, key })
+    }
+}
+
+/// Parse a lowercase key name
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.is_ascii_alphanumeric().then_some(Key::Char(c));
+    }
+
+    match name {
+        "return" => Some(Key::Enter),
+        "escape" => Some(Key::Escape),
+        "del" => Some(Key::Delete),
+        "page_up" | "prior" => Some(Key::PageUp),
+        "page_down" | "next" => Some(Key::PageDown),
+        _ => KEY_NAMES
+            .iter()
+            .find(|(_, key_name, _)| *key_name == name)
+            .map(|&(key, _, _)| key),
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier.name())?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_combo() {
+        let combo: KeyCombo = "ctrl+shift+T".parse().unwrap();
+        assert_eq!(combo.modifiers, vec![Modifier::Ctrl, Modifier::Shift]);
+        assert_eq!(combo.key, Key::Char('t'));
+        assert_eq!(combo.to_string(), "ctrl+shift+t");
+
+        assert_eq!(
+            "Return".parse::<KeyCombo>().unwrap(),
+            KeyCombo::key(Key::Enter)
+        );
+        assert_eq!("BackSpace".parse::<KeyCombo>().unwrap().key, Key::BackSpace);
+        assert_eq!("super + 1".parse::<KeyCombo>().unwrap().key, Key::Char('1'));
+
+        assert!("hyper+a".parse::<KeyCombo>().is_err());
+        assert!("ctrl+".parse::<KeyCombo>().is_err());
+        assert!("ctrl+f13".parse::<KeyCombo>().is_err());
+        assert!("ctrl+é".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_key_names() {
+        assert_eq!(Key::Char('a').code(), 30);
+        assert_eq!(Key::Char('v').code(), 47);
+        assert_eq!(Key::Char('0').code(), 11);
+        assert_eq!(Key::Enter.code(), 28);
+        assert_eq!(Key::PageUp.keysym(), "Prior");
+        assert_eq!(Key::Char('a').keysym(), "a");
+        assert_eq!(Key::Escape.to_string(), "esc");
+    }
+}
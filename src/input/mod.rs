@@ -1,3 +1,10 @@
+pub mod clipboard;
+pub mod dotool;
 pub mod inject;
+pub mod keymap;
+pub mod keys;
+pub mod tools;
+pub mod uinput;
 
-pub use inject::TextInjector;
+pub use inject::{Action, Injector, TextInjector};
+pub use keys::{Key, KeyCombo};
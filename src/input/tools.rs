@@ -0,0 +1,204 @@
+use crate::error::{Result, ScribeError};
+use crate::input::inject::Injector;
+use crate::input::keys::KeyCombo;
+use std::process::{Command, Stdio};
+
+/// Command-line typing tool run once per injection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypingTool {
+    /// ydotool, talking to the ydotoold daemon (uinput, any session)
+    Ydotool,
+    /// wtype, using the virtual keyboard protocol of wlroots compositors
+    Wtype,
+    /// xdotool, using XTEST on X11
+    Xdotool,
+}
+
+impl TypingTool {
+    /// Binary to run
+    #[must_use]
+    pub const fn program(self) -> &'static str {
+        match self {
+            Self::Ydotool => "ydotool",
+            Self::Wtype => "wtype",
+            Self::Xdotool => "xdotool",
+        }
+    }
+
+    /// Arguments that type `text` with `delay_ms` between keystrokes
+    fn type_args(self, text: &str, delay_ms: u64) -> Vec<String> {
+        let delay = delay_ms.to_string();
+        let args: &[&str] = match self {
+            Self::Ydotool => &["type", "--key-delay", &delay, "--"],
+            Self::Wtype => &["-d", &delay, "--"],
+            Self::Xdotool => &["type", "--clearmodifiers", "--delay", &delay, "--"],
+        };
+        args.iter()
+            .copied()
+            .chain(std::iter::once(text))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Arguments that press `combo`
+    pub(crate) fn key_args(self, combo: &KeyCombo) -> Vec<String> {
+        match self {
+            // Linux input event codes, pressed (:1) and released (:0) in order
+            Self::Ydotool => {
+                let codes: Vec<u16> = combo
+                    .modifiers
+                    .iter()
+                    .map(|modifier| modifier.code())
+                    .chain(std::iter::once(combo.key.code()))
+                    .collect();
+                std::iter::once("key".to_string())
+                    .chain(codes.iter().map(|code| format!("{code}:1")))
+                    .chain(codes.iter().rev().map(|code| format!("{code}:0")))
+                    .collect()
+            }
+            Self::Wtype => {
+                // wtype calls the Super modifier "logo"
+                let name = |modifier: &crate::input::keys::Modifier| match modifier.name() {
+                    "super" => "logo",
+                    name => name,
+                };
+                let mut args = Vec::new();
+                for modifier in &combo.modifiers {
+                    args.extend(["-M".to_string(), name(modifier).to_string()]);
+                }
+                args.extend(["-k".to_string(), combo.key.keysym()]);
+                for modifier in combo.modifiers.iter().rev() {
+                    args.extend(["-m".to_string(), name(modifier).to_string()]);
+                }
+                args
+            }
+            Self::Xdotool => {
+                let keys: Vec<String> = combo
+                    .modifiers
+                    .iter()
+                    .map(|modifier| modifier.name().to_string())
+                    .chain(std::iter::once(combo.key.keysym()))
+                    .collect();
+                vec![
+                    "key".to_string(),
+                    "--clearmodifiers".to_string(),
+                    keys.join("+"),
+                ]
+            }
+        }
+    }
+}
+
+/// Run `program` with `args`, failing on a non-zero exit status
+pub(crate) fn run(program: &str, args: &[String]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| ScribeError::Injection(format!("Failed to run {program}: {e}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(ScribeError::Injection(format!(
+            "{program} failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Require `program` to be installed
+pub(crate) fn require(program: &str) -> Result<()> {
+    which::which(program).map(|_| ()).map_err(|_| {
+        ScribeError::Injection(format!(
+            "{program} binary not found in PATH. Install {program} or choose another injection method"
+        ))
+    })
+}
+
+/// Text injector that runs a typing tool for each injection
+#[derive(Debug)]
+pub struct ToolInjector {
+    tool: TypingTool,
+    delay_ms: u64,
+}
+
+impl ToolInjector {
+    /// Create an injector for `tool`
+    ///
+    /// # Errors
+    /// - Returns error if the tool's binary is not found in PATH
+    pub fn new(tool: TypingTool, delay_ms: u64) -> Result<Self> {
+        require(tool.program())?;
+        Ok(Self { tool, delay_ms })
+    }
+}
+
+impl Injector for ToolInjector {
+    fn name(&self) -> &'static str {
+        self.tool.program()
+    }
+
+    fn inject(&mut self, text: &str) -> Result<()> {
+        run(
+            self.tool.program(),
+            &self.tool.type_args(text, self.delay_ms),
+        )
+    }
+
+    fn press(&mut self, combo: &KeyCombo) -> Result<()> {
+        run(self.tool.program(), &self.tool.key_args(combo))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_args_end_options() {
+        // Text starting with '-' must not be parsed as an option
+        for tool in [TypingTool::Ydotool, TypingTool::Wtype, TypingTool::Xdotool] {
+            let args = tool.type_args("-rf hello", 5);
+            assert_eq!(args[args.len() - 2], "--");
+            assert_eq!(args[args.len() - 1], "-rf hello");
+            assert!(args.contains(&"5".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_key_args() {
+        let paste = KeyCombo::paste();
+        assert_eq!(
+            TypingTool::Ydotool.key_args(&paste),
+            ["key", "29:1", "47:1", "47:0", "29:0"]
+        );
+        assert_eq!(
+            TypingTool::Wtype.key_args(&paste),
+            ["-M", "ctrl", "-k", "v", "-m", "ctrl"]
+        );
+        assert_eq!(
+            TypingTool::Xdotool.key_args(&paste),
+            ["key", "--clearmodifiers", "ctrl+v"]
+        );
+
+        let combo: KeyCombo = "super+shift+enter".parse().unwrap();
+        assert_eq!(
+            TypingTool::Wtype.key_args(&combo),
+            ["-M", "logo", "-M", "shift", "-k", "Return", "-m", "shift", "-m", "logo"]
+        );
+        assert_eq!(
+            TypingTool::Xdotool.key_args(&combo),
+            ["key", "--clearmodifiers", "super+shift+Return"]
+        );
+    }
+
+    #[test]
+    fn test_new_missing_tool() {
+        if which::which("xdotool").is_err() {
+            let err = ToolInjector::new(TypingTool::Xdotool, 2).unwrap_err();
+            assert!(err.to_string().contains("xdotool binary not found"));
+        }
+    }
+}
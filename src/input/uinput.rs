@@ -0,0 +1,205 @@
+use crate::error::{Result, ScribeError};
+use crate::input::inject::Injector;
+use crate::input::keymap::{KeyStroke, Keymap};
+use crate::input::keys::{Key, KeyCombo};
+use nix::libc;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+
+const UINPUT_PATH: &str = "/dev/uinput";
+const DEVICE_NAME: &[u8] = b"scribe virtual keyboard";
+
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const SYN_REPORT: u16 = 0;
+const BUS_VIRTUAL: u16 = 0x06;
+
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_RIGHTALT: u16 = 100;
+/// Highest key code the virtual keyboard announces
+const KEY_MAX_USED: u16 = 127;
+
+/// Time for the compositor to pick up a new input device
+const SETTLE_DELAY: Duration = Duration::from_millis(200);
+
+nix::ioctl_none!(ui_dev_create, b'U', 1);
+nix::ioctl_none!(ui_dev_destroy, b'U', 2);
+nix::ioctl_write_ptr!(ui_dev_setup, b'U', 3, libc::uinput_setup);
+nix::ioctl_write_int!(ui_set_evbit, b'U', 100);
+nix::ioctl_write_int!(ui_set_keybit, b'U', 101);
+
+/// Virtual keyboard created through `/dev/uinput`
+#[derive(Debug)]
+struct VirtualKeyboard {
+    file: File,
+}
+
+impl VirtualKeyboard {
+    fn create() -> Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .open(UINPUT_PATH)
+            .map_err(|e| {
+                ScribeError::Injection(format!(
+                    "Failed to open {UINPUT_PATH}: {e}. Add yourself to the 'input' group or install the udev rule"
+                ))
+            })?;
+        let fd = file.as_raw_fd();
+
+        let mut setup = libc::uinput_setup {
+            id: libc::input_id {
+                bustype: BUS_VIRTUAL,
+                vendor: 0,
+                product: 0,
+                version: 1,
+            },
+            name: [0; libc::UINPUT_MAX_NAME_SIZE],
+            ff_effects_max: 0,
+        };
+        for (dst, &src) in setup.name.iter_mut().zip(DEVICE_NAME) {
+            *dst = libc::c_char::from_ne_bytes([src]);
+        }
+
+        // SAFETY: `fd` is an open uinput device and `setup` outlives the call
+        unsafe {
+            ui_set_evbit(fd, EV_KEY.into()).map_err(ioctl_error)?;
+            for code in 1..=KEY_MAX_USED {
+                ui_set_keybit(fd, code.into()).map_err(ioctl_error)?;
+            }
+            ui_dev_setup(fd, &raw const setup).map_err(ioctl_error)?;
+            ui_dev_create(fd).map_err(ioctl_error)?;
+        }
+
+        std::thread::sleep(SETTLE_DELAY);
+        Ok(Self { file })
+    }
+
+    fn emit(&mut self, kind: u16, code: u16, value: i32) -> Result<()> {
+        let event = libc::input_event {
+            time: libc::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+            type_: kind,
+            code,
+            value,
+        };
+        // SAFETY: `input_event` is a plain C struct without padding
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                (&raw const event).cast::<u8>(),
+                std::mem::size_of::<libc::input_event>(),
+            )
+        };
+        self.file
+            .write_all(bytes)
+            .map_err(|e| ScribeError::Injection(format!("Failed to write input event: {e}")))
+    }
+
+    fn key(&mut self, code: u16, pressed: bool) -> Result<()> {
+        self.emit(EV_KEY, code, i32::from(pressed))?;
+        self.emit(EV_SYN, SYN_REPORT, 0)
+    }
+
+    fn stroke(&mut self, stroke: KeyStroke, delay: Duration) -> Result<()> {
+        let modifiers: Vec<u16> = [
+            (stroke.ctrl, KEY_LEFTCTRL),
+            (stroke.shift, KEY_LEFTSHIFT),
+            (stroke.altgr, KEY_RIGHTALT),
+        ]
+        .iter()
+        .filter(|(held, _)| *held)
+        .map(|&(_, code)| code)
+        .collect();
+        self.press(&modifiers, stroke.code, delay)
+    }
+
+    /// Press `code` while holding the `modifiers` keys
+    fn press(&mut self, modifiers: &[u16], code: u16, delay: Duration) -> Result<()> {
+        for &modifier in modifiers {
+            self.key(modifier, true)?;
+        }
+        self.key(code, true)?;
+        self.key(code, false)?;
+        for &modifier in modifiers.iter().rev() {
+            self.key(modifier, false)?;
+        }
+
+        std::thread::sleep(delay);
+        Ok(())
+    }
+}
+
+impl Drop for VirtualKeyboard {
+    fn drop(&mut self) {
+        // SAFETY: `file` is the uinput device created in `create`
+        if let Err(e) = unsafe { ui_dev_destroy(self.file.as_raw_fd()) } {
+            tracing::warn!("Failed to destroy virtual keyboard: {e}");
+        }
+    }
+}
+
+fn ioctl_error(e: nix::Error) -> ScribeError {
+    ScribeError::Injection(format!("Failed to set up virtual keyboard: {e}"))
+}
+
+/// Built-in text injector typing through a virtual uinput keyboard
+///
+/// Characters are mapped to keys with `injection.layout`, which must match the
+/// layout of the session.
+#[derive(Debug)]
+pub struct UinputInjector {
+    keyboard: VirtualKeyboard,
+    keymap: Keymap,
+    delay: Duration,
+}
+
+impl UinputInjector {
+    /// Create the virtual keyboard and load the XKB `layout`
+    ///
+    /// # Errors
+    /// - Returns error if the layout cannot be loaded
+    /// - Returns error if `/dev/uinput` cannot be opened or set up
+    pub fn new(layout: &str, delay_ms: u64) -> Result<Self> {
+        let keymap = Keymap::load(layout)?;
+        Ok(Self {
+            keyboard: VirtualKeyboard::create()?,
+            keymap,
+            delay: Duration::from_millis(delay_ms),
+        })
+    }
+}
+
+impl Injector for UinputInjector {
+    fn name(&self) -> &'static str {
+        "uinput"
+    }
+
+    fn inject(&mut self, text: &str) -> Result<()> {
+        // Map everything first so an unmappable character types nothing
+        for stroke in self.keymap.strokes(text)? {
+            self.keyboard.stroke(stroke, self.delay)?;
+        }
+        Ok(())
+    }
+
+    fn press(&mut self, combo: &KeyCombo) -> Result<()> {
+        // Shortcuts follow the layout: Ctrl+Z is the key that types 'z'
+        let code = match combo.key {
+            Key::Char(c) => self
+                .keymap
+                .get(c)
+                .map_or_else(|| combo.key.code(), |stroke| stroke.code),
+            key => key.code(),
+        };
+        let modifiers: Vec<u16> = combo
+            .modifiers
+            .iter()
+            .map(|modifier| modifier.code())
+            .collect();
+        self.keyboard.press(&modifiers, code, self.delay)
+    }
+}
@@ -1,6 +1,7 @@
 pub mod client;
 pub mod server;
 
+use crate::transcription::Task;
 use serde::{Deserialize, Serialize};
 
 /// IPC Commands
@@ -11,16 +12,60 @@ pub enum Command {
     Stop,
     Cancel,
     Status,
+    /// `Toggle`, with a task for the recording it starts
+    ToggleTask(Task),
+    /// `Start`, with a task for this recording
+    StartTask(Task),
+}
+
+impl Command {
+    /// Task requested for the recording this command starts, if any
+    #[must_use]
+    pub const fn task(&self) -> Option<Task> {
+        match self {
+            Self::ToggleTask(task) | Self::StartTask(task) => Some(*task),
+            _ => None,
+        }
+    }
 }
 
 /// IPC Responses
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum Response {
     Ok,
-    Status(AppStatus),
+    Status(StatusReport),
     Error(String),
 }
 
+/// Daemon status returned by the `Status` command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatusReport {
+    pub state: AppStatus,
+    /// Language auto-detected in the most recent transcription
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl StatusReport {
+    #[must_use]
+    pub const fn new(state: AppStatus) -> Self {
+        Self {
+            state,
+            language: None,
+        }
+    }
+}
+
+impl std::fmt::Display for StatusReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.state)?;
+        if let Some(language) = &self.language {
+            write!(f, " (language: {language})")?;
+        }
+        Ok(())
+    }
+}
+
 /// Application status
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum AppStatus {
@@ -42,6 +87,8 @@ mod tests {
             Command::Stop,
             Command::Cancel,
             Command::Status,
+            Command::ToggleTask(Task::Translate),
+            Command::StartTask(Task::Transcribe),
         ];
 
         for cmd in commands {
@@ -55,9 +102,13 @@ mod tests {
     fn test_response_serialization() {
         let responses = vec![
             Response::Ok,
-            Response::Status(AppStatus::Idle),
-            Response::Status(AppStatus::Recording),
-            Response::Status(AppStatus::Transcribing),
+            Response::Status(StatusReport::new(AppStatus::Idle)),
+            Response::Status(StatusReport::new(AppStatus::Recording)),
+            Response::Status(StatusReport::new(AppStatus::Transcribing)),
+            Response::Status(StatusReport {
+                state: AppStatus::Idle,
+                language: Some("no".to_string()),
+            }),
             Response::Error("test error".to_string()),
         ];
 
@@ -96,6 +147,10 @@ mod tests {
         let cmd = Command::Status;
         let json = serde_json::to_string(&cmd).expect("Failed to serialize");
         assert_eq!(json, r#""Status""#);
+
+        let cmd = Command::ToggleTask(Task::Translate);
+        let json = serde_json::to_string(&cmd).expect("Failed to serialize");
+        assert_eq!(json, r#"{"ToggleTask":"translate"}"#);
     }
 
     #[test]
@@ -104,8 +159,20 @@ mod tests {
         let json = serde_json::to_string(&resp).expect("Failed to serialize");
         assert_eq!(json, r#""Ok""#);
 
-        let resp = Response::Status(AppStatus::Recording);
+        let resp = Response::Status(StatusReport::new(AppStatus::Recording));
         let json = serde_json::to_string(&resp).expect("Failed to serialize");
         assert!(json.contains("Recording"));
+        assert!(!json.contains("language"));
+    }
+
+    #[test]
+    fn test_status_report_display() {
+        assert_eq!(StatusReport::new(AppStatus::Idle).to_string(), "Idle");
+
+        let report = StatusReport {
+            state: AppStatus::Transcribing,
+            language: Some("no".to_string()),
+        };
+        assert_eq!(report.to_string(), "Transcribing (language: no)");
     }
 }
@@ -1,5 +1,5 @@
 use crate::error::{Result, ScribeError};
-use crate::ipc::{AppStatus, Command, Response};
+use crate::ipc::{AppStatus, Command, Response, StatusReport};
 use std::path::PathBuf;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{UnixListener, UnixStream};
@@ -9,7 +9,7 @@ use tokio::sync::{mpsc, oneshot};
 pub struct IpcServer {
     socket_path: PathBuf,
     command_tx: mpsc::Sender<Command>,
-    status_rx: mpsc::Receiver<AppStatus>,
+    status_rx: mpsc::Receiver<StatusReport>,
     ready_tx: Option<oneshot::Sender<()>>,
 }
 
@@ -21,7 +21,7 @@ impl IpcServer {
     /// * `status_rx` - Channel to receive status updates from main event loop
     pub fn new(
         command_tx: mpsc::Sender<Command>,
-        status_rx: mpsc::Receiver<AppStatus>,
+        status_rx: mpsc::Receiver<StatusReport>,
     ) -> Result<Self> {
         let socket_path = Self::socket_path()?;
         Ok(Self {
@@ -91,7 +91,7 @@ impl IpcServer {
         }
 
         // Store current status
-        let mut current_status = AppStatus::Idle;
+        let mut current_status = StatusReport::new(AppStatus::Idle);
 
         loop {
             tokio::select! {
@@ -125,7 +125,7 @@ impl IpcServer {
     async fn handle_client(
         mut stream: UnixStream,
         command_tx: mpsc::Sender<Command>,
-        current_status: AppStatus,
+        current_status: StatusReport,
     ) -> Result<()> {
         let mut buf = vec![0u8; 1024];
         let n = stream
@@ -8,6 +8,7 @@ pub mod input;
 pub mod ipc;
 pub mod models;
 pub mod notifications;
+pub mod postprocess;
 pub mod transcription;
 pub mod tray;
 
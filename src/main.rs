@@ -1,16 +1,23 @@
 #![allow(clippy::multiple_crate_versions)] // TODO: Resolve dependency conflicts in Phase 1+
 
 use clap::{Parser, Subcommand};
-use scribe::audio::capture::AudioCapture;
+use scribe::audio::capture::{AudioCapture, AudioStream};
+use scribe::audio::{resample, SpeechSegmenter, VadConfig, VoiceActivityDetector};
 use scribe::config::Config;
 use scribe::error::{Result, ScribeError};
 use scribe::input::inject::TextInjector;
-use scribe::ipc::{client::IpcClient, server::IpcServer, AppStatus, Command, Response};
-use scribe::transcription::Backend;
+use scribe::ipc::{
+    client::IpcClient, server::IpcServer, AppStatus, Command, Response, StatusReport,
+};
+use scribe::notifications::NotificationManager;
+use scribe::postprocess::PostProcessor;
+use scribe::transcription::{self, Backend, Task};
 use scribe::tray::TrayIcon;
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use tokio::signal;
 use tokio::sync::mpsc;
+use tokio::task::{JoinError, JoinHandle};
 
 #[derive(Parser)]
 #[command(name = "scribe")]
@@ -26,9 +33,17 @@ enum Commands {
     /// Start the daemon (default)
     Daemon,
     /// Toggle recording on/off
-    Toggle,
+    Toggle {
+        /// Transcribe or translate this recording, overriding the configured task
+        #[arg(long)]
+        task: Option<Task>,
+    },
     /// Start recording
-    Start,
+    Start {
+        /// Transcribe or translate this recording, overriding the configured task
+        #[arg(long)]
+        task: Option<Task>,
+    },
     /// Stop recording
     Stop,
     /// Cancel recording without transcription
@@ -49,7 +64,13 @@ enum ModelCommands {
     /// List available models for download
     ListAvailable,
     /// Download a model from `HuggingFace`
-    Download { name: String },
+    Download {
+        name: String,
+        /// Install quantized weights (`q4_0`, `q5_0` or `q8_0`), downloading
+        /// published GGUF weights or quantizing locally when there are none
+        #[arg(long)]
+        quantize: Option<String>,
+    },
     /// Set active model (updates config)
     Set { name: String },
     /// Remove an installed model
@@ -80,8 +101,12 @@ async fn main() -> Result<()> {
             tracing::info!("Starting Scribe daemon");
             run_daemon(config).await
         }
-        Some(Commands::Toggle) => run_client(Command::Toggle).await,
-        Some(Commands::Start) => run_client(Command::Start).await,
+        Some(Commands::Toggle { task }) => {
+            run_client(task.map_or(Command::Toggle, Command::ToggleTask)).await
+        }
+        Some(Commands::Start { task }) => {
+            run_client(task.map_or(Command::Start, Command::StartTask)).await
+        }
         Some(Commands::Stop) => run_client(Command::Stop).await,
         Some(Commands::Cancel) => run_client(Command::Cancel).await,
         Some(Commands::Status) => run_client(Command::Status).await,
@@ -89,14 +114,30 @@ async fn main() -> Result<()> {
     }
 }
 
-/// Application state machine
+/// How captured audio is handled while recording
+enum RecordingMode {
+    /// Collect all frames and transcribe when recording stops
+    Manual,
+    /// Transcribe each utterance while recording continues
+    Streaming(SpeechSegmenter),
+    /// Collect all frames and stop once speech is followed by silence
+    AutoStop(SpeechSegmenter),
+}
+
+/// Recording state machine
+///
+/// Transcription runs in background jobs, so a new recording can start while
+/// earlier recordings are still being transcribed.
 enum AppState {
     Idle,
     Recording {
-        audio_stream: scribe::audio::capture::AudioStream,
+        /// Identifies the recording's transcription jobs
+        id: u64,
+        audio_stream: AudioStream,
         frames: Vec<Vec<i16>>,
+        mode: RecordingMode,
+        task: Task,
     },
-    Transcribing,
 }
 
 impl std::fmt::Debug for AppState {
@@ -104,23 +145,194 @@ impl std::fmt::Debug for AppState {
         match self {
             Self::Idle => write!(f, "Idle"),
             Self::Recording { frames, .. } => write!(f, "Recording(frames: {})", frames.len()),
-            Self::Transcribing => write!(f, "Transcribing"),
         }
     }
 }
 
+/// Transcription of captured audio, running as a background task
+///
+/// Cancelling aborts the task before any text is injected. The local backend
+/// notices the dropped task at its next decoding step and stops, so the model is
+/// free for the next job right away.
+type TranscriptionJob = JoinHandle<Result<Option<String>>>;
+
+/// Audio captured for transcription
+enum Audio {
+    /// Frames of a whole recording, discarded if shorter than `min_duration_ms`
+    Recording(Vec<Vec<i16>>),
+    /// Utterance cut from a streaming recording by the VAD
+    Utterance(Vec<i16>),
+}
+
+/// Audio waiting for its turn to be transcribed
+struct PendingJob {
+    /// Recording the audio belongs to, so Cancel can discard all of it
+    recording: u64,
+    audio: Audio,
+    task: Task,
+}
+
+/// Transcription jobs, run one at a time in the order the audio was captured
+///
+/// Whole recordings and streamed utterances share the queue, so text is
+/// injected in order and Cancel and the Transcribing status cover both.
+struct JobQueue {
+    config: Config,
+    backend: Arc<Backend>,
+    text_injector: Arc<Mutex<TextInjector>>,
+    postprocessor: Arc<PostProcessor>,
+    /// Running job and the recording it belongs to
+    running: Option<(u64, TranscriptionJob)>,
+    queued: VecDeque<PendingJob>,
+}
+
+impl JobQueue {
+    fn new(
+        config: &Config,
+        backend: &Arc<Backend>,
+        text_injector: &Arc<Mutex<TextInjector>>,
+        postprocessor: &Arc<PostProcessor>,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            backend: Arc::clone(backend),
+            text_injector: Arc::clone(text_injector),
+            postprocessor: Arc::clone(postprocessor),
+            running: None,
+            queued: VecDeque::new(),
+        }
+    }
+
+    /// Whether a job is running; queued jobs only wait behind a running one
+    const fn is_busy(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Recording of the running job
+    fn running_recording(&self) -> Option<u64> {
+        self.running.as_ref().map(|(recording, _)| *recording)
+    }
+
+    /// Run `job` now, or queue it behind the running job
+    fn push(&mut self, job: PendingJob) {
+        if self.running.is_some() {
+            tracing::debug!(
+                queued = self.queued.len() + 1,
+                "Queued behind current transcription"
+            );
+            self.queued.push_back(job);
+        } else {
+            let recording = job.recording;
+            self.running = Some((recording, self.spawn(job)));
+        }
+    }
+
+    /// Wait for the running job and start the next one; pending while idle
+    async fn finished(&mut self) -> std::result::Result<Result<Option<String>>, JoinError> {
+        let Some((_, job)) = &mut self.running else {
+            return std::future::pending().await;
+        };
+        let result = job.await;
+        self.start_next();
+        result
+    }
+
+    /// Discard the jobs of `recording`, aborting it if it is running
+    fn cancel_recording(&mut self, recording: u64) {
+        self.queued.retain(|job| job.recording != recording);
+
+        if self.running_recording() == Some(recording) {
+            if let Some((_, job)) = self.running.take() {
+                job.abort();
+            }
+            self.start_next();
+        }
+    }
+
+    /// Abort the running job and drop the queued ones
+    fn abort_all(&mut self) {
+        self.queued.clear();
+        if let Some((_, job)) = self.running.take() {
+            job.abort();
+        }
+    }
+
+    fn start_next(&mut self) {
+        self.running = self.queued.pop_front().map(|job| {
+            tracing::debug!(
+                remaining = self.queued.len(),
+                "Starting queued transcription"
+            );
+            (job.recording, self.spawn(job))
+        });
+    }
+
+    /// Spawn background task that transcribes `job` and injects the text
+    fn spawn(&self, job: PendingJob) -> TranscriptionJob {
+        let config = self.config.clone();
+        let backend = Arc::clone(&self.backend);
+        let text_injector = Arc::clone(&self.text_injector);
+        let postprocessor = Arc::clone(&self.postprocessor);
+
+        tokio::spawn(async move {
+            match job.audio {
+                Audio::Recording(frames) => {
+                    process_recording(
+                        frames,
+                        job.task,
+                        &config,
+                        &backend,
+                        &text_injector,
+                        &postprocessor,
+                    )
+                    .await
+                }
+                Audio::Utterance(audio) => {
+                    transcribe_and_inject(
+                        &audio,
+                        job.task,
+                        config.audio.sample_rate,
+                        &backend,
+                        &text_injector,
+                        &postprocessor,
+                    )
+                    .await
+                }
+            }
+        })
+    }
+}
+
+/// Status reported over IPC and in the tray
+///
+/// Recording takes precedence over a transcription running in the background.
+const fn daemon_status(app_state: &AppState, transcribing: bool) -> AppStatus {
+    match app_state {
+        AppState::Recording { .. } => AppStatus::Recording,
+        AppState::Idle if transcribing => AppStatus::Transcribing,
+        AppState::Idle => AppStatus::Idle,
+    }
+}
+
 #[allow(clippy::too_many_lines)] // Complex state machine requires many lines
 #[allow(clippy::future_not_send)] // Not spawning across threads, runs in main event loop
 async fn run_daemon(config: Config) -> Result<()> {
     tracing::info!("Initializing components");
 
+    // Desktop notifications respect [notifications] config
+    let notifications = NotificationManager::new(config.notifications.clone());
+
     // Initialize transcription backend
     tracing::debug!(
         backend = %config.transcription.backend,
         model = %config.transcription.model,
         "Loading transcription backend"
     );
-    let backend = Backend::from_config(&config.transcription).await?;
+    let backend = Arc::new(
+        Backend::from_config(&config.transcription)
+            .await
+            .inspect_err(|e| notifications.error(e))?,
+    );
     tracing::info!(
         backend = %backend.backend_name(),
         "Transcription backend initialized"
@@ -132,12 +344,17 @@ async fn run_daemon(config: Config) -> Result<()> {
         delay_ms = config.injection.delay_ms,
         "Initializing text injector"
     );
-    let mut text_injector = TextInjector::new(config.injection.delay_ms)?;
-    tracing::info!("Text injector initialized");
+    let text_injector =
+        TextInjector::from_config(&config.injection).inspect_err(|e| notifications.error(e))?;
+    tracing::info!(method = text_injector.method(), "Text injector initialized");
+    let text_injector = Arc::new(Mutex::new(text_injector));
+    let postprocessor = Arc::new(
+        PostProcessor::from_config(&config.postprocess).inspect_err(|e| notifications.error(e))?,
+    );
 
     // Create channels for IPC communication
     let (command_tx, mut command_rx) = mpsc::channel::<Command>(32);
-    let (status_tx, status_rx) = mpsc::channel::<AppStatus>(32);
+    let (status_tx, status_rx) = mpsc::channel::<StatusReport>(32);
     tracing::debug!("IPC channels created");
 
     // Start IPC server in background
@@ -169,14 +386,18 @@ async fn run_daemon(config: Config) -> Result<()> {
 
     // Application state
     let mut app_state = AppState::Idle;
-    let mut current_status = AppStatus::Idle;
+    let mut current_status = StatusReport::new(AppStatus::Idle);
+
+    // Transcription of finished recordings and streamed utterances
+    let mut jobs = JobQueue::new(&config, &backend, &text_injector, &postprocessor);
+    let mut recording_count: u64 = 0;
 
     // Helper to update both IPC and tray status
-    let update_status = |status: AppStatus| {
+    let update_status = |status: StatusReport| {
         // Update tray status and signal refresh
         tray_handle.update(|tray| {
             if let Ok(mut tray_status) = tray.status_handle().lock() {
-                *tray_status = status.clone();
+                *tray_status = status.state.clone();
             }
         });
         status_tx.send(status)
@@ -211,121 +432,77 @@ async fn run_daemon(config: Config) -> Result<()> {
             Some(cmd) = command_rx.recv() => {
                 tracing::debug!("Received command: {:?}", cmd);
 
-                match cmd {
-                    Command::Toggle => {
-                        tracing::debug!(state = ?app_state, "Processing Toggle command");
-                        match &mut app_state {
-                            AppState::Idle => {
-                                // Start recording
-                                tracing::info!("Toggle: starting recording");
-                                match start_recording(&config) {
-                                    Ok((stream, frames)) => {
-                                        tracing::info!("Recording started successfully");
-                                        app_state = AppState::Recording { audio_stream: stream, frames };
-                                        current_status = AppStatus::Recording;
-                                        update_status(current_status.clone()).await.ok();
-                                    }
-                                    Err(e) => {
-                                        tracing::error!(error = %e, "Failed to start recording");
-                                    }
-                                }
-                            }
-                            AppState::Recording { .. } => {
-                                // Stop recording and transcribe
-                                tracing::info!("Toggle: stopping recording");
-                                if let AppState::Recording { audio_stream, frames } =
-                                    std::mem::replace(&mut app_state, AppState::Transcribing)
-                                {
-                                    audio_stream.stop();
-                                    tracing::info!(
-                                        frame_count = frames.len(),
-                                        "Recording stopped, processing audio"
-                                    );
-                                    current_status = AppStatus::Transcribing;
-                                    update_status(current_status.clone()).await.ok();
-
-                                    // Process recording
-                                    match process_recording(frames, &config, &backend, &mut text_injector).await {
-                                        Ok(Some(text)) => {
-                                            tracing::info!(
-                                                text_length = text.len(),
-                                                text = %text,
-                                                "Transcription and injection successful"
-                                            );
-                                        }
-                                        Ok(None) => {
-                                            tracing::info!("No speech detected in recording");
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(error = %e, "Transcription failed");
-                                        }
-                                    }
-                                    current_status = AppStatus::Idle;
-                                    update_status(current_status.clone()).await.ok();
-
-                                    app_state = AppState::Idle;
-                                    tracing::debug!("Returned to idle state");
-                                }
-                            }
-                            AppState::Transcribing => {
-                                tracing::warn!("Ignoring toggle command: currently transcribing");
-                            }
-                        }
+                // Resolve Toggle into Start/Stop based on current state
+                let cmd = match (cmd, &app_state) {
+                    (Command::Toggle, AppState::Idle) => Command::Start,
+                    (Command::ToggleTask(task), AppState::Idle) => Command::StartTask(task),
+                    (Command::Toggle | Command::ToggleTask(_), AppState::Recording { .. }) => {
+                        Command::Stop
                     }
+                    (cmd, _) => cmd,
+                };
 
-                    Command::Start => {
+                match cmd {
+                    Command::Start
+                    | Command::StartTask(_)
+                    | Command::Toggle
+                    | Command::ToggleTask(_) => {
                         tracing::debug!(state = ?app_state, "Processing Start command");
                         if matches!(app_state, AppState::Idle) {
-                            tracing::info!("Starting recording");
-                            match start_recording(&config) {
-                                Ok((stream, frames)) => {
+                            tracing::info!(
+                                transcribing = jobs.is_busy(),
+                                "Starting recording"
+                            );
+                            let task = cmd
+                                .task()
+                                .unwrap_or_else(|| Task::from_config(&config.transcription));
+                            recording_count += 1;
+                            match start_recording(&config, task, recording_count) {
+                                Ok(state) => {
                                     tracing::info!("Recording started successfully");
-                                    app_state = AppState::Recording { audio_stream: stream, frames };
-                                    current_status = AppStatus::Recording;
-                                    update_status(current_status.clone()).await.ok();
+                                    app_state = state;
+                                    notifications.recording_started();
                                 }
                                 Err(e) => {
                                     tracing::error!(error = %e, "Failed to start recording");
+                                    notifications.error(&e);
                                 }
                             }
                         } else {
-                            tracing::warn!(state = ?app_state, "Cannot start: not in idle state");
+                            tracing::warn!(state = ?app_state, "Cannot start: already recording");
                         }
                     }
 
                     Command::Stop => {
                         tracing::debug!(state = ?app_state, "Processing Stop command");
-                        if let AppState::Recording { audio_stream, frames } =
-                            std::mem::replace(&mut app_state, AppState::Transcribing)
+                        if let AppState::Recording { id, audio_stream, frames, mode, task } =
+                            std::mem::replace(&mut app_state, AppState::Idle)
                         {
                             audio_stream.stop();
-                            tracing::info!(
-                                frame_count = frames.len(),
-                                "Recording stopped, processing audio"
-                            );
-                            current_status = AppStatus::Transcribing;
-                            update_status(current_status.clone()).await.ok();
-
-                            // Process recording synchronously
-                            match process_recording(frames, &config, &backend, &mut text_injector).await {
-                                Ok(Some(text)) => {
-                                    tracing::info!(
-                                        text_length = text.len(),
-                                        text = %text,
-                                        "Transcription and injection successful"
-                                    );
-                                }
-                                Ok(None) => {
-                                    tracing::info!("No speech detected in recording");
-                                }
-                                Err(e) => {
-                                    tracing::error!(error = %e, "Transcription failed");
+                            notifications.recording_stopped();
+
+                            if let RecordingMode::Streaming(mut segmenter) = mode {
+                                // Streaming: queue the trailing utterance behind the others
+                                if let Some(utterance) = segmenter.flush() {
+                                    jobs.push(PendingJob {
+                                        recording: id,
+                                        audio: Audio::Utterance(utterance),
+                                        task,
+                                    });
                                 }
+                                tracing::info!("Streaming recording stopped");
+                            } else {
+                                tracing::info!(
+                                    frame_count = frames.len(),
+                                    transcribing = jobs.is_busy(),
+                                    "Recording stopped, queueing audio for transcription"
+                                );
+                                jobs.push(PendingJob {
+                                    recording: id,
+                                    audio: Audio::Recording(frames),
+                                    task,
+                                });
                             }
-                            current_status = AppStatus::Idle;
-                            update_status(current_status.clone()).await.ok();
-
-                            app_state = AppState::Idle;
                             tracing::debug!("Returned to idle state");
                         } else {
                             tracing::warn!(state = ?app_state, "Cannot stop: not currently recording");
@@ -334,19 +511,22 @@ async fn run_daemon(config: Config) -> Result<()> {
 
                     Command::Cancel => {
                         tracing::debug!(state = ?app_state, "Processing Cancel command");
-                        if let AppState::Recording { audio_stream, .. } =
+                        if let AppState::Recording { id, audio_stream, .. } =
                             std::mem::replace(&mut app_state, AppState::Idle)
                         {
                             audio_stream.stop();
+                            // Streamed utterances of this recording are not typed either
+                            jobs.cancel_recording(id);
                             tracing::info!("Recording cancelled, discarding audio");
-                            current_status = AppStatus::Idle;
-                            update_status(current_status.clone()).await.ok();
-
-                            // TODO: Integrate NotificationManager and call:
-                            // notification_manager.recording_cancelled();
+                            notifications.recording_cancelled();
                             tracing::debug!("Returned to idle state without transcription");
+                        } else if let Some(id) = jobs.running_recording() {
+                            // Discards the whole recording, including utterances still queued
+                            jobs.cancel_recording(id);
+                            tracing::info!("Transcription cancelled, discarding audio");
+                            notifications.recording_cancelled();
                         } else {
-                            tracing::warn!(state = ?app_state, "Cannot cancel: not currently recording");
+                            tracing::warn!(state = ?app_state, "Cannot cancel: not recording or transcribing");
                         }
                     }
 
@@ -356,40 +536,114 @@ async fn run_daemon(config: Config) -> Result<()> {
                 }
             }
 
+            // Finish the running transcription job and start the next one
+            result = jobs.finished() => {
+                match result {
+                    Ok(Ok(Some(text))) => {
+                        tracing::info!(
+                            text_length = text.len(),
+                            text = %text,
+                            "Transcription and injection successful"
+                        );
+                        notifications.transcription_complete(&text);
+                    }
+                    Ok(Ok(None)) => {
+                        tracing::info!("No speech detected");
+                    }
+                    Ok(Err(e)) => {
+                        tracing::error!(error = %e, "Transcription failed");
+                        notifications.error(&e);
+                    }
+                    Err(e) => {
+                        tracing::error!(error = %e, "Transcription task failed");
+                    }
+                }
+            }
+
             // Collect audio frames while recording
             frame = async {
                 match &mut app_state {
-                    AppState::Recording { audio_stream, frames } => {
-                        audio_stream.recv().await.map(|f| (f, frames))
+                    AppState::Recording { id, audio_stream, frames, mode, task } => {
+                        audio_stream.recv().await.map(|f| (f, frames, mode, *task, *id))
                     }
-                    _ => {
+                    AppState::Idle => {
                         // Sleep indefinitely when not recording to avoid busy loop
-                        std::future::pending::<Option<(Vec<i16>, &mut Vec<Vec<i16>>)>>().await
+                        std::future::pending().await
                     }
                 }
             } => {
-                if let Some((frame, frames)) = frame {
-                    frames.push(frame);
-                    if frames.len() % 100 == 0 {
-                        tracing::trace!(frame_count = frames.len(), "Collecting audio frames");
+                if let Some((frame, frames, mode, task, id)) = frame {
+                    match mode {
+                        RecordingMode::Streaming(segmenter) => match segmenter.push_frame(&frame) {
+                            Ok(Some(utterance)) => {
+                                tracing::debug!(
+                                    sample_count = utterance.len(),
+                                    "Utterance complete, queueing for transcription"
+                                );
+                                jobs.push(PendingJob {
+                                    recording: id,
+                                    audio: Audio::Utterance(utterance),
+                                    task,
+                                });
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                tracing::warn!(error = %e, "VAD failed to process frame");
+                            }
+                        },
+                        RecordingMode::AutoStop(segmenter) => {
+                            match segmenter.push_frame(&frame) {
+                                Ok(Some(_)) => {
+                                    // Reuse the regular stop path
+                                    tracing::info!("Silence after speech detected, stopping recording");
+                                    if let Err(e) = command_tx.try_send(Command::Stop) {
+                                        tracing::error!(error = %e, "Failed to queue auto-stop");
+                                    }
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    tracing::warn!(error = %e, "VAD failed to process frame");
+                                }
+                            }
+                            frames.push(frame);
+                        }
+                        RecordingMode::Manual => {
+                            frames.push(frame);
+                            if frames.len() % 100 == 0 {
+                                tracing::trace!(frame_count = frames.len(), "Collecting audio frames");
+                            }
+                        }
                     }
                 }
             }
         }
+
+        // Publish status changes from recording or transcription transitions
+        let status = StatusReport {
+            state: daemon_status(&app_state, jobs.is_busy()),
+            language: backend.detected_language(),
+        };
+        if status != current_status {
+            current_status = status;
+            update_status(current_status.clone()).await.ok();
+        }
     }
 
+    // Cancel transcription still in flight
+    jobs.abort_all();
+
     // Cleanup
     tracing::info!("Cleaning up resources");
-    text_injector.cleanup();
+    if let Ok(mut injector) = text_injector.lock() {
+        injector.cleanup();
+    }
     tracing::info!("Shutdown complete");
 
     Ok(())
 }
 
-/// Start audio recording
-fn start_recording(
-    config: &Config,
-) -> Result<(scribe::audio::capture::AudioStream, Vec<Vec<i16>>)> {
+/// Start audio recording `id` for `task`
+fn start_recording(config: &Config, task: Task, id: u64) -> Result<AppState> {
     tracing::debug!(
         sample_rate = config.audio.sample_rate,
         device = ?config.audio.device,
@@ -398,22 +652,60 @@ fn start_recording(
     let audio_capture =
         AudioCapture::new(config.audio.sample_rate, config.audio.device.as_deref())?;
 
+    // Streaming and auto-stop modes split audio into utterances as it is captured
+    let new_segmenter = || SpeechSegmenter::new(&vad_config(config, audio_capture.sample_rate()));
+    let mode = match config.recording.mode.as_str() {
+        "streaming" => RecordingMode::Streaming(new_segmenter()?),
+        "auto_stop" => RecordingMode::AutoStop(new_segmenter()?),
+        _ => RecordingMode::Manual,
+    };
+
     let audio_stream = audio_capture.start_recording()?;
-    let frames = Vec::new();
 
-    tracing::debug!("Audio stream started");
-    Ok((audio_stream, frames))
+    tracing::debug!(mode = %config.recording.mode, %task, "Audio stream started");
+    Ok(AppState::Recording {
+        id,
+        audio_stream,
+        frames: Vec::new(),
+        mode,
+        task,
+    })
 }
 
-/// Process recorded frames: VAD extraction -> transcription -> text injection
-#[allow(clippy::future_not_send)] // Not spawning across threads, runs in main event loop
+/// VAD settings from `[vad]` for audio at `sample_rate`
+const fn vad_config(config: &Config, sample_rate: u32) -> VadConfig {
+    VadConfig {
+        sample_rate,
+        aggressiveness: config.vad.aggressiveness,
+        silence_ms: config.vad.silence_ms,
+        min_duration_ms: config.vad.min_duration_ms,
+        skip_initial_ms: config.vad.skip_initial_ms,
+    }
+}
+
+/// Whether the VAD detects speech anywhere in recorded `frames`
+fn contains_speech(frames: &[Vec<i16>], config: &Config) -> Result<bool> {
+    VoiceActivityDetector::new(&vad_config(config, config.audio.sample_rate))?
+        .contains_speech(frames.iter().map(Vec::as_slice))
+}
+
+/// Process recorded frames: VAD speech check -> transcription -> text injection
 async fn process_recording(
     frames: Vec<Vec<i16>>,
+    task: Task,
     config: &Config,
     backend: &Backend,
-    text_injector: &mut TextInjector,
+    text_injector: &Mutex<TextInjector>,
+    postprocessor: &PostProcessor,
 ) -> Result<Option<String>> {
-    // Flatten all frames into single audio buffer (bypass VAD extraction for manual toggle)
+    // Whisper hallucinates phrases like "Thank you." on silence, so recordings
+    // without speech are never transcribed
+    if !contains_speech(&frames, config)? {
+        tracing::debug!("No speech detected in recording, discarding");
+        return Ok(None);
+    }
+
+    // Transcribe the whole recording: VAD extraction would end at the first pause
     let audio: Vec<i16> = frames.into_iter().flatten().collect();
 
     #[allow(clippy::cast_precision_loss)]
@@ -438,16 +730,65 @@ async fn process_recording(
         "Processing recording for transcription"
     );
 
-    // Transcribe
-    let text = backend.transcribe(&audio).await?;
+    transcribe_and_inject(
+        &audio,
+        task,
+        config.audio.sample_rate,
+        backend,
+        text_injector,
+        postprocessor,
+    )
+    .await
+}
+
+/// Transcribe audio, post-process the text and inject it
+async fn transcribe_and_inject(
+    audio: &[i16],
+    task: Task,
+    sample_rate: u32,
+    backend: &Backend,
+    text_injector: &Mutex<TextInjector>,
+    postprocessor: &PostProcessor,
+) -> Result<Option<String>> {
+    // Backends expect 16kHz audio
+    let transcription = if sample_rate == transcription::SAMPLE_RATE {
+        backend.transcribe_segments(audio, task).await?
+    } else {
+        tracing::debug!(
+            from = sample_rate,
+            to = transcription::SAMPLE_RATE,
+            "Resampling audio for transcription"
+        );
+        let audio = resample(audio, sample_rate, transcription::SAMPLE_RATE);
+        backend.transcribe_segments(&audio, task).await?
+    };
+
+    for segment in &transcription.segments {
+        tracing::debug!(
+            start = %format!("{:.2}", segment.start),
+            end = %format!("{:.2}", segment.end),
+            avg_logprob = segment.avg_logprob,
+            text = %segment.text,
+            "Transcribed segment"
+        );
+    }
+    // Fix systematic mis-transcriptions before anything acts on the text
+    let text = postprocessor.rewrite(&transcription.text);
+    if text != transcription.text {
+        tracing::debug!(original = %transcription.text, text = %text, "Applied replacement rules");
+    }
 
     if text.trim().is_empty() {
         tracing::debug!("Transcription returned empty text");
         Ok(None)
     } else {
-        // Inject text
-        tracing::debug!(text = %text, "Injecting transcribed text");
-        text_injector.inject(&text)?;
+        // Inject text, with spoken commands turned into key presses
+        let actions = postprocessor.actions(&text);
+        tracing::debug!(text = %text, ?actions, "Injecting transcribed text");
+        text_injector
+            .lock()
+            .map_err(|_| ScribeError::Injection("Text injector lock poisoned".to_string()))?
+            .perform(&actions)?;
         Ok(Some(text))
     }
 }
@@ -464,7 +805,7 @@ async fn run_client(cmd: Command) -> Result<()> {
         }
         Response::Status(status) => {
             tracing::debug!(status = ?status, "Received status");
-            println!("{status:?}");
+            println!("{status}");
         }
         Response::Error(e) => {
             tracing::error!(error = %e, "Command failed");
@@ -479,7 +820,7 @@ async fn run_client(cmd: Command) -> Result<()> {
 /// Handle model management commands
 #[allow(clippy::too_many_lines)]
 async fn run_model_command(command: ModelCommands) -> Result<()> {
-    use scribe::models::{ModelInfo, ModelManager};
+    use scribe::models::{ModelInfo, ModelManager, ModelSource};
 
     match command {
         ModelCommands::List => {
@@ -499,7 +840,12 @@ async fn run_model_command(command: ModelCommands) -> Result<()> {
                 let is_active = active == Some(model.name.as_str());
                 let marker = if is_active { " (active)" } else { "" };
                 let size_mb = model.size_bytes / 1_000_000;
-                println!("  {} - {} MB{}", model.name, size_mb, marker);
+                let custom = if model.source.is_some() {
+                    " [custom]"
+                } else {
+                    ""
+                };
+                println!("  {} - {} MB{}{}", model.name, size_mb, custom, marker);
             }
 
             if active.is_none() {
@@ -519,64 +865,68 @@ async fn run_model_command(command: ModelCommands) -> Result<()> {
                     ""
                 };
                 println!(
-                    "  {:8} - {:>6} MB  {:>5} params  {}{}",
+                    "  {:16} - {:>6} MB  {:>5} params  {}{}",
                     model.name, model.size_mb, model.parameters, model.description, recommended
                 );
+                for weights in model.quantized {
+                    println!(
+                        "  {:16}   {:>6} MB  {} weights: --quantize {}",
+                        "", weights.size_mb, weights.quantization, weights.quantization
+                    );
+                }
             }
 
             println!("\nDownload a model:");
-            println!("  scribe model download <name>");
+            println!("  scribe model download <name> [--quantize q4_0|q5_0|q8_0]");
+            println!("\nCustom models:");
+            println!("  scribe model download <owner>/<repo>[@revision]");
+            println!("  scribe model set /path/to/model-dir");
         }
 
-        ModelCommands::Download { name } => {
+        ModelCommands::Download { name, quantize } => {
             let mut manager = ModelManager::new()?;
-
-            // Find model info
-            let model_info = ModelInfo::find(&name).ok_or_else(|| {
-                let suggestion = ModelInfo::suggest(&name);
-                let mut msg = format!("Unknown model: '{name}'");
-                if let Some(suggestion) = suggestion {
-                    msg.push_str("\n  Did you mean: ");
-                    msg.push_str(suggestion);
-                    msg.push('?');
+            let source = ModelSource::parse(&name)?;
+            let name = source.name();
+
+            let quantization = quantize.as_deref().unwrap_or("none");
+            match &source {
+                // Local directories are used in place and only need quantizing
+                ModelSource::Directory(_) if quantization != "none" => {
+                    manager.quantize(&name, quantization)?;
                 }
-                msg.push_str("\n  Available: ");
-                msg.push_str(&ModelInfo::all_names().join(", "));
-                ScribeError::NotFound(msg)
-            })?;
-
-            println!(
-                "Downloading {} model ({} MB)...",
-                model_info.name, model_info.size_mb
-            );
-            manager.download(model_info).await?;
-            println!("✓ Model '{}' downloaded successfully", model_info.name);
+                ModelSource::Registry(model_info) => {
+                    let size_mb = model_info
+                        .quantized_weights(quantization)
+                        .map_or(model_info.size_mb, |weights| weights.size_mb);
+                    println!("Downloading {name} model ({size_mb} MB)...");
+                    manager.download(&source, quantization).await?;
+                    println!("✓ Model '{name}' downloaded successfully");
+                }
+                _ => {
+                    println!("Downloading {name} model...");
+                    manager.download(&source, quantization).await?;
+                    println!("✓ Model '{name}' downloaded successfully");
+                }
+            }
+
+            if let Some(quantization) = quantize {
+                println!("\nUse the quantized weights by setting in config:");
+                println!("  quantization = \"{quantization}\"");
+            }
 
             // Suggest setting it as active if no active model
             if manager.get_active().is_none() {
                 println!("\nSet as active model:");
-                println!("  scribe model set {}", model_info.name);
+                println!("  scribe model set {name}");
             }
         }
 
         ModelCommands::Set { name } => {
             let mut manager = ModelManager::new()?;
 
-            // Check if model exists in registry
-            if ModelInfo::find(&name).is_none() {
-                let suggestion = ModelInfo::suggest(&name);
-                let mut msg = format!("Unknown model: '{name}'");
-                if let Some(suggestion) = suggestion {
-                    msg.push_str("\n  Did you mean: ");
-                    msg.push_str(suggestion);
-                    msg.push('?');
-                }
-                msg.push_str("\n  Available: ");
-                msg.push_str(&ModelInfo::all_names().join(", "));
-                return Err(ScribeError::NotFound(msg));
-            }
-
-            manager.set_active(&name)?;
+            // Validates the name against the registry and custom model formats
+            let source = ModelSource::parse(&name)?;
+            manager.set_active(&source.name())?;
         }
 
         ModelCommands::Remove { name } => {
@@ -610,17 +960,19 @@ async fn run_model_command(command: ModelCommands) -> Result<()> {
                     println!("\nDownload with:");
                     println!("  scribe model download {name}");
                 }
-            } else {
-                let suggestion = ModelInfo::suggest(&name);
-                let mut msg = format!("Unknown model: '{name}'");
-                if let Some(suggestion) = suggestion {
-                    msg.push_str("\n  Did you mean: ");
-                    msg.push_str(suggestion);
-                    msg.push('?');
+            } else if let Some(installed) = manager.get_installed_info(&name) {
+                println!("Model: {}", installed.name);
+                if let Some(source) = &installed.source {
+                    println!("Source: {source}");
                 }
-                msg.push_str("\n  Available: ");
-                msg.push_str(&ModelInfo::all_names().join(", "));
-                return Err(ScribeError::NotFound(msg));
+                println!("\nInstalled:");
+                println!("  Size: {} MB", installed.size_bytes / 1_000_000);
+                println!("  Downloaded: {}", installed.downloaded_at);
+                if manager.get_active() == Some(&name) {
+                    println!("  Active: Yes");
+                }
+            } else {
+                return Err(ModelInfo::unknown_model_error(&name));
             }
         }
     }
@@ -1,9 +1,13 @@
 use crate::error::{Result, ScribeError};
-use crate::models::manifest::{models_data_dir, InstalledModel};
-use crate::models::registry::ModelInfo;
-use hf_hub::{api::tokio::Api, Repo, RepoType};
+use crate::models::manifest::{
+    dir_size, model_dir_name, models_data_dir, InstalledModel, ModelFiles,
+};
+use crate::models::quantize;
+use crate::models::source::ModelSource;
+use hf_hub::api::tokio::{Api, ApiRepo};
+use hf_hub::{Repo, RepoType};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Model downloader with progress tracking
 pub struct ModelDownloader {
@@ -20,11 +24,42 @@ impl ModelDownloader {
     }
 
     /// Download a model from `HuggingFace` Hub
-    pub async fn download(&self, model_info: &ModelInfo) -> Result<InstalledModel> {
+    ///
+    /// With a `quantization` other than "none", published GGUF weights are
+    /// downloaded instead of the f32 weights. Models without them are downloaded
+    /// in full and quantized locally.
+    pub async fn download(
+        &self,
+        source: &ModelSource,
+        quantization: &str,
+    ) -> Result<InstalledModel> {
+        let name = source.name();
+        let (repo_id, revision, size_mb, published) = match source {
+            ModelSource::Registry(info) => {
+                let published = info.quantized_weights(quantization);
+                (
+                    info.hf_repo.to_string(),
+                    info.hf_revision.to_string(),
+                    published.map_or(info.size_mb, |weights| weights.size_mb),
+                    published,
+                )
+            }
+            // Size of custom models is unknown, only the safety buffer is checked
+            ModelSource::HuggingFace { repo, revision } => {
+                (repo.clone(), revision.clone(), 0, None)
+            }
+            ModelSource::Directory(path) => {
+                return Err(ScribeError::Config(format!(
+                    "Local model directories are used in place and need no download. Activate it with:\n  scribe model set {}",
+                    path.display()
+                )))
+            }
+        };
+
         // Check disk space
-        self.check_disk_space(model_info.size_mb)?;
+        self.check_disk_space(size_mb)?;
 
-        tracing::info!("Downloading {} model from HuggingFace...", model_info.name);
+        tracing::info!("Downloading {name} model from HuggingFace...");
 
         // Use hf-hub to download the model
         let api = Api::new().map_err(|e| {
@@ -33,57 +68,64 @@ impl ModelDownloader {
             )))
         })?;
 
-        let repo = api.repo(Repo::with_revision(
-            model_info.hf_repo.to_string(),
-            RepoType::Model,
-            model_info.hf_revision.to_string(),
-        ));
+        let repo = api.repo(Repo::with_revision(repo_id, RepoType::Model, revision));
 
         // Download required files with progress indication
-        println!("Downloading {} model files...", model_info.name);
-
-        let config_path = repo.get("config.json").await.map_err(|e| {
-            ScribeError::Transcription(crate::error::TranscriptionError::ModelError(format!(
-                "Failed to download config.json: {e}"
-            )))
-        })?;
-
-        let tokenizer_path = repo.get("tokenizer.json").await.map_err(|e| {
-            ScribeError::Transcription(crate::error::TranscriptionError::ModelError(format!(
-                "Failed to download tokenizer.json: {e}"
-            )))
-        })?;
-
-        let weights_path = repo.get("model.safetensors").await.map_err(|e| {
-            ScribeError::Transcription(crate::error::TranscriptionError::ModelError(format!(
-                "Failed to download model.safetensors: {e}"
-            )))
-        })?;
+        println!("Downloading {name} model files...");
+
+        let config_path = download_file(&repo, ModelFiles::CONFIG).await?;
+        let tokenizer_path = download_file(&repo, ModelFiles::TOKENIZER).await?;
+
+        // Install into the models directory so the daemon can load it offline
+        let model_dir = self.model_path(&name);
+        fs::create_dir_all(&model_dir)?;
+        let files = ModelFiles::in_dir(&model_dir);
+        install_file(&config_path, &files.config)?;
+        install_file(&tokenizer_path, &files.tokenizer)?;
+
+        let quantized = files.clone().with_quantization(quantization);
+        if let Some(weights) = published {
+            let repo = api.repo(Repo::with_revision(
+                weights.hf_repo.to_string(),
+                RepoType::Model,
+                weights.hf_revision.to_string(),
+            ));
+            let weights_path = download_file(&repo, weights.file).await?;
+            install_file(&weights_path, &quantized.weights)?;
+        } else {
+            let weights_path = download_file(&repo, ModelFiles::WEIGHTS).await?;
+            install_file(&weights_path, &files.weights)?;
+
+            if quantization != "none" {
+                println!(
+                    "No {quantization} weights are published for {name}, quantizing locally..."
+                );
+                quantize::quantize_weights(
+                    &files.weights,
+                    &quantized.weights,
+                    quantize::ggml_dtype(quantization)?,
+                )?;
+            }
+        }
 
-        // Calculate total size of downloaded files
-        let config_size = fs::metadata(&config_path).map_or(0, |m| m.len());
-        let tokenizer_size = fs::metadata(&tokenizer_path).map_or(0, |m| m.len());
-        let weights_size = fs::metadata(&weights_path).map_or(0, |m| m.len());
-        let total_size = config_size + tokenizer_size + weights_size;
+        // Size of everything installed for this model, across quantizations
+        let total_size = dir_size(&model_dir);
 
         tracing::info!(
-            "Downloaded {} model successfully ({} MB)",
-            model_info.name,
+            "Downloaded {name} model successfully ({} MB)",
             total_size / 1_000_000
         );
 
-        println!(
-            "✓ Downloaded {} model ({} MB)",
-            model_info.name,
-            total_size / 1_000_000
-        );
+        println!("✓ Downloaded {name} model ({} MB)", total_size / 1_000_000);
 
         // Create installed model record
+        let source = matches!(source, ModelSource::HuggingFace { .. }).then(|| name.clone());
         Ok(InstalledModel {
-            name: model_info.name.to_string(),
+            name,
             size_bytes: total_size,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source,
         })
     }
 
@@ -114,10 +156,39 @@ impl ModelDownloader {
     /// Get path where model files would be stored
     #[must_use]
     pub fn model_path(&self, model_name: &str) -> PathBuf {
-        self.models_dir.join(format!("whisper-{model_name}"))
+        self.models_dir.join(model_dir_name(model_name))
     }
 }
 
+/// Download `file` from `repo` into the `HuggingFace` cache
+async fn download_file(repo: &ApiRepo, file: &str) -> Result<PathBuf> {
+    repo.get(file).await.map_err(|e| {
+        ScribeError::Transcription(crate::error::TranscriptionError::ModelError(format!(
+            "Failed to download {file}: {e}"
+        )))
+    })
+}
+
+/// Place a downloaded file at `dest`, hard-linking the cached blob when possible
+///
+/// hf-hub cache entries are symlinks into a blob store, so the link target is
+/// resolved first. Falls back to copying when the cache is on another filesystem.
+fn install_file(src: &Path, dest: &Path) -> Result<()> {
+    let blob = fs::canonicalize(src)?;
+
+    // Write under a temporary name so an interrupted install is never picked up
+    let tmp_path = dest.with_extension("tmp");
+    if tmp_path.exists() {
+        fs::remove_file(&tmp_path)?;
+    }
+    if fs::hard_link(&blob, &tmp_path).is_err() {
+        fs::copy(&blob, &tmp_path)?;
+    }
+    fs::rename(&tmp_path, dest)?;
+
+    Ok(())
+}
+
 /// Format bytes as human-readable string
 #[must_use]
 #[allow(clippy::cast_precision_loss)]
@@ -153,6 +224,25 @@ mod tests {
         assert_eq!(format_bytes(1_610_612_736), "1.50 GB");
     }
 
+    #[test]
+    fn test_install_file_resolves_symlinks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let blob = temp_dir.path().join("blob");
+        let link = temp_dir.path().join("link");
+        let dest = temp_dir.path().join("model.safetensors");
+
+        fs::write(&blob, "weights").unwrap();
+        std::os::unix::fs::symlink(&blob, &link).unwrap();
+
+        install_file(&link, &dest).unwrap();
+        assert!(!fs::symlink_metadata(&dest).unwrap().is_symlink());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "weights");
+
+        // Reinstalling over an existing file succeeds
+        install_file(&link, &dest).unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "weights");
+    }
+
     #[test]
     fn test_model_path() {
         let downloader = ModelDownloader::new().unwrap();
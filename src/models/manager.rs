@@ -1,9 +1,13 @@
 use crate::config::schema::Config;
 use crate::error::{Result, ScribeError};
 use crate::models::download::ModelDownloader;
-use crate::models::manifest::{manifest_path, models_data_dir, InstalledModel, Manifest};
-use crate::models::registry::ModelInfo;
+use crate::models::manifest::{
+    manifest_path, model_dir, models_data_dir, InstalledModel, Manifest, ModelFiles,
+};
+use crate::models::quantize;
+use crate::models::source::ModelSource;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Model manager for installing, removing, and switching models
 pub struct ModelManager {
@@ -44,18 +48,31 @@ impl ModelManager {
     }
 
     /// Download and install a model
-    pub async fn download(&mut self, model_info: &ModelInfo) -> Result<()> {
-        // Check if already installed
-        if self.manifest.find_model(model_info.name).is_some() {
+    ///
+    /// With a `quantization` other than "none", published GGUF weights are
+    /// installed in place of the f32 weights, or quantized locally if the model
+    /// has none.
+    pub async fn download(&mut self, source: &ModelSource, quantization: &str) -> Result<()> {
+        if quantization != "none" {
+            quantize::ggml_dtype(quantization)?;
+        }
+        let name = source.name();
+
+        // Check if already installed (models whose files went missing can be reinstalled)
+        if self.manifest.find_model(&name).is_some()
+            && ModelFiles::in_dir(&model_dir(&name)?)
+                .with_quantization(quantization)
+                .missing()
+                .is_empty()
+        {
             return Err(ScribeError::Config(format!(
-                "Model '{}' is already installed. Use 'scribe model set {}' to activate it.",
-                model_info.name, model_info.name
+                "Model '{name}' is already installed. Use 'scribe model set {name}' to activate it."
             )));
         }
 
         // Download the model
         let downloader = ModelDownloader::new()?;
-        let installed = downloader.download(model_info).await?;
+        let installed = downloader.download(source, quantization).await?;
 
         // Add to manifest
         self.manifest.add_model(installed);
@@ -65,7 +82,13 @@ impl ModelManager {
     }
 
     /// Set active model (update config + manifest)
+    ///
+    /// Local model directories are added to the manifest the first time they are set.
     pub fn set_active(&mut self, model_name: &str) -> Result<()> {
+        if let ModelSource::Directory(dir) = ModelSource::parse(model_name)? {
+            return self.set_active_directory(&dir);
+        }
+
         // Verify model is installed
         if self.manifest.find_model(model_name).is_none() {
             return Err(ScribeError::NotFound(format!(
@@ -84,6 +107,42 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Register a local model directory in the manifest and activate it
+    ///
+    /// The directory must hold the weights for the configured
+    /// `transcription.quantization`: safetensors for "none", GGUF otherwise.
+    fn set_active_directory(&mut self, dir: &Path) -> Result<()> {
+        let dir = fs::canonicalize(dir).map_err(|e| {
+            ScribeError::NotFound(format!(
+                "Model directory '{}' not found: {e}",
+                dir.display()
+            ))
+        })?;
+        let quantization = Config::load()?.transcription.quantization;
+        let files = checked_files(&dir, &ModelSource::Directory(dir.clone()), &quantization)?;
+        let name = dir.display().to_string();
+
+        let size_bytes = [&files.config, &files.tokenizer, &files.weights]
+            .iter()
+            .map(|p| fs::metadata(p).map_or(0, |m| m.len()))
+            .sum();
+
+        self.manifest.add_model(InstalledModel {
+            name: name.clone(),
+            size_bytes,
+            checksum: None,
+            downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: Some(name.clone()),
+        });
+        self.manifest.set_active(&name)?;
+        self.save_manifest()?;
+
+        Self::update_config_model(&name)?;
+
+        println!("✓ Active model set to '{name}'");
+        Ok(())
+    }
+
     /// Remove an installed model
     pub fn remove(&mut self, model_name: &str) -> Result<()> {
         // Check if it's the active model
@@ -99,9 +158,16 @@ impl ModelManager {
         })?;
 
         let size_bytes = model.size_bytes;
-
-        // Models are managed by hf-hub cache, so we just remove from manifest
-        // The actual files are in the HuggingFace cache directory
+        let is_directory = model.source.as_deref().is_some_and(|source| {
+            matches!(ModelSource::parse(source), Ok(ModelSource::Directory(_)))
+        });
+
+        // Delete downloaded files, then drop the manifest entry
+        // Local model directories belong to the user and are left untouched
+        let dir = model_dir(model_name)?;
+        if !is_directory && dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
         self.manifest.remove_model(model_name)?;
         self.save_manifest()?;
 
@@ -109,7 +175,7 @@ impl ModelManager {
             "✓ Removed model '{model_name}' (freed {} MB)",
             size_bytes / 1_000_000
         );
-        println!("Note: Model files remain in HuggingFace cache. Clear with: rm -rf ~/.cache/huggingface");
+        println!("Note: Download cache remains in ~/.cache/huggingface. Clear with: rm -rf ~/.cache/huggingface");
 
         Ok(())
     }
@@ -126,6 +192,62 @@ impl ModelManager {
         self.manifest.find_model(model_name)
     }
 
+    /// Get file paths of the model configured as `transcription.model`, with
+    /// weights for `transcription.quantization`
+    ///
+    /// Local directories are loaded in place; registry and `HuggingFace` models must
+    /// have been installed with `scribe model download`. Never touches the network.
+    pub fn resolve(&self, spec: &str, quantization: &str) -> Result<ModelFiles> {
+        let source = ModelSource::parse(spec)?;
+        match &source {
+            ModelSource::Directory(dir) => checked_files(dir, &source, quantization),
+            _ => self.installed_files(&source.name(), quantization),
+        }
+    }
+
+    /// Create quantized GGUF weights from the f32 weights of a model directory
+    /// or installed model
+    ///
+    /// Returns the path of the written file, which the local backend loads when
+    /// `transcription.quantization` matches.
+    pub fn quantize(&self, spec: &str, quantization: &str) -> Result<PathBuf> {
+        let dtype = quantize::ggml_dtype(quantization)?;
+        let files = self.resolve(spec, "none")?;
+        let output = files.clone().with_quantization(quantization).weights;
+
+        println!("Quantizing '{spec}' to {quantization}...");
+        let size_bytes = quantize::quantize_weights(&files.weights, &output, dtype)?;
+        println!(
+            "✓ Wrote {} ({} MB)",
+            output.display(),
+            size_bytes / 1_000_000
+        );
+
+        Ok(output)
+    }
+
+    /// Get file paths of an installed model
+    ///
+    /// Fails with download instructions if the model is not installed or its files
+    /// are missing, so callers never need network access to load a model.
+    pub fn installed_files(&self, model_name: &str, quantization: &str) -> Result<ModelFiles> {
+        let model = self.manifest.find_model(model_name).ok_or_else(|| {
+            ScribeError::NotFound(format!(
+                "Model '{model_name}' is not installed. Download it first:\n  scribe model download {}",
+                download_args(model_name, quantization)
+            ))
+        })?;
+
+        let source = model
+            .source
+            .as_deref()
+            .map_or_else(|| ModelSource::parse(model_name), ModelSource::parse)?;
+        match &source {
+            ModelSource::Directory(dir) => checked_files(dir, &source, quantization),
+            _ => checked_files(&model_dir(model_name)?, &source, quantization),
+        }
+    }
+
     /// Save manifest to disk
     fn save_manifest(&self) -> Result<()> {
         self.manifest.save(&self.manifest_path)
@@ -169,6 +291,57 @@ impl ModelManager {
     }
 }
 
+/// Files of the model from `source` in `dir`, failing if any are missing
+///
+/// Downloaded models can be reinstalled; a local directory must be fixed by
+/// whoever put the model there, or quantized if only the GGUF weights are missing.
+fn checked_files(dir: &Path, source: &ModelSource, quantization: &str) -> Result<ModelFiles> {
+    let files = ModelFiles::in_dir(dir).with_quantization(quantization);
+    let missing = files.missing();
+    if missing.is_empty() {
+        return Ok(files);
+    }
+
+    let missing = missing
+        .iter()
+        .filter_map(|p| p.file_name()?.to_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = match source {
+        // Only the quantized weights are missing
+        ModelSource::Directory(_) if ModelFiles::in_dir(dir).missing().is_empty() => format!(
+            "Model directory {} has no {quantization} weights. Create them with:\n  scribe model download {} --quantize {quantization}",
+            dir.display(),
+            dir.display()
+        ),
+        ModelSource::Directory(_) => format!(
+            "Model directory {} is missing {missing}. It must contain {}, {} and {} (quantization = \"{quantization}\")",
+            dir.display(),
+            ModelFiles::CONFIG,
+            ModelFiles::TOKENIZER,
+            files.weights.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        ModelSource::Registry(_) | ModelSource::HuggingFace { .. } => {
+            format!(
+                "Model '{}' is incomplete: missing {missing} in {}. Reinstall it:\n  scribe model download {}",
+                source.name(),
+                dir.display(),
+                download_args(&source.name(), quantization)
+            )
+        }
+    };
+    Err(ScribeError::NotFound(message))
+}
+
+/// Arguments of `scribe model download` that install `name` for `quantization`
+fn download_args(name: &str, quantization: &str) -> String {
+    if quantization == "none" {
+        name.to_string()
+    } else {
+        format!("{name} --quantize {quantization}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +367,73 @@ mod tests {
         let _ = manager.get_active();
     }
 
+    #[test]
+    fn test_installed_files_not_installed() {
+        let manager = ModelManager {
+            manifest: Manifest::default(),
+            manifest_path: std::path::PathBuf::from("/nonexistent/manifest.json"),
+        };
+
+        let err = manager
+            .installed_files("base", "none")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not installed"));
+        assert!(err.contains("scribe model download base"));
+    }
+
+    #[test]
+    fn test_resolve_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = ModelManager {
+            manifest: Manifest::default(),
+            manifest_path: temp_dir.path().join("manifest.json"),
+        };
+        let spec = temp_dir.path().display().to_string();
+
+        // Incomplete directory lists what is missing
+        fs::write(temp_dir.path().join(ModelFiles::CONFIG), "{}").unwrap();
+        let err = manager.resolve(&spec, "none").unwrap_err().to_string();
+        assert!(err.contains("tokenizer.json, model.safetensors"));
+        assert!(err.contains(&spec));
+        assert!(!err.contains("scribe model download"));
+
+        // Complete directory loads in place without a manifest entry
+        fs::write(temp_dir.path().join(ModelFiles::TOKENIZER), "{}").unwrap();
+        fs::write(temp_dir.path().join(ModelFiles::WEIGHTS), "weights").unwrap();
+        let files = manager.resolve(&spec, "none").unwrap();
+        assert_eq!(files, ModelFiles::in_dir(temp_dir.path()));
+
+        // Quantized weights are created from the f32 weights, never downloaded
+        let err = manager.resolve(&spec, "q5_0").unwrap_err().to_string();
+        assert!(err.contains(&format!("scribe model download {spec} --quantize q5_0")));
+        fs::write(temp_dir.path().join("model-q5_0.gguf"), "weights").unwrap();
+        let files = manager.resolve(&spec, "q5_0").unwrap();
+        assert_eq!(files.weights, temp_dir.path().join("model-q5_0.gguf"));
+
+        // GGUF weights alone are enough when quantized weights are configured
+        fs::remove_file(temp_dir.path().join(ModelFiles::WEIGHTS)).unwrap();
+        assert!(manager.resolve(&spec, "q5_0").is_ok());
+        let err = manager.resolve(&spec, "none").unwrap_err().to_string();
+        assert!(err.contains("model.safetensors"));
+        let err = manager.resolve(&spec, "q8_0").unwrap_err().to_string();
+        assert!(err.contains("model-q8_0.gguf"));
+    }
+
+    #[test]
+    fn test_resolve_huggingface_not_installed() {
+        let manager = ModelManager {
+            manifest: Manifest::default(),
+            manifest_path: std::path::PathBuf::from("/nonexistent/manifest.json"),
+        };
+
+        let err = manager
+            .resolve("acme/whisper-medical@v2", "q8_0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("scribe model download acme/whisper-medical@v2 --quantize q8_0"));
+    }
+
     #[test]
     fn test_is_installed() {
         let manager = ModelManager::new().unwrap();
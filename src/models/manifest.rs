@@ -1,4 +1,5 @@
 use crate::error::{Result, ScribeError};
+use crate::models::quantize::QUANTIZATIONS;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -10,6 +11,73 @@ pub struct InstalledModel {
     pub size_bytes: u64,
     pub checksum: Option<String>,
     pub downloaded_at: String,
+    /// Origin of a custom model: `HuggingFace` `repo@revision` or local directory
+    ///
+    /// `None` for registry models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Files making up an installed model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelFiles {
+    pub config: PathBuf,
+    pub tokenizer: PathBuf,
+    pub weights: PathBuf,
+}
+
+impl ModelFiles {
+    /// Model file names, as published on `HuggingFace`
+    pub const CONFIG: &'static str = "config.json";
+    pub const TOKENIZER: &'static str = "tokenizer.json";
+    pub const WEIGHTS: &'static str = "model.safetensors";
+
+    /// Expected file locations inside a model directory
+    #[must_use]
+    pub fn in_dir(dir: &Path) -> Self {
+        Self {
+            config: dir.join(Self::CONFIG),
+            tokenizer: dir.join(Self::TOKENIZER),
+            weights: dir.join(Self::WEIGHTS),
+        }
+    }
+
+    /// Files loaded with `transcription.quantization`
+    ///
+    /// Unless it is "none", the weights are GGUF weights quantized to
+    /// `quantization` (e.g. `model-q5_0.gguf`) next to the safetensors file.
+    #[must_use]
+    pub fn with_quantization(mut self, quantization: &str) -> Self {
+        if quantization != "none" {
+            self.weights = self
+                .weights
+                .with_file_name(format!("model-{quantization}.gguf"));
+        }
+        self
+    }
+
+    /// Whether `dir` holds a complete model, with f32 or any quantized weights
+    #[must_use]
+    pub fn complete_in(dir: &Path) -> bool {
+        std::iter::once("none")
+            .chain(QUANTIZATIONS.iter().copied())
+            .any(|quantization| {
+                Self::in_dir(dir)
+                    .with_quantization(quantization)
+                    .missing()
+                    .is_empty()
+            })
+    }
+
+    /// Files that do not exist on disk
+    #[must_use]
+    pub fn missing(&self) -> Vec<&Path> {
+        [&self.config, &self.tokenizer, &self.weights]
+            .into_iter()
+            .filter(|path| !path.is_file())
+            .map(PathBuf::as_path)
+            .collect()
+    }
 }
 
 /// Manifest tracking installed models
@@ -127,12 +195,30 @@ impl Manifest {
             return Ok(manifest);
         }
 
-        // Scan for .safetensors files
+        // Scan for "whisper-{name}" model directories and .safetensors files
         let entries = fs::read_dir(models_dir)?;
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if let Some(ext) = path.extension() {
+            if path.is_dir() {
+                let name = path.file_name().and_then(|n| n.to_str());
+                if let Some(dir_name) = name.and_then(|n| n.strip_prefix("whisper-")) {
+                    if ModelFiles::complete_in(&path) {
+                        // Custom HuggingFace models are stored as "whisper-{owner}--{name}"
+                        let model_name = dir_name.replace("--", "/");
+                        let source = model_name.contains('/').then(|| model_name.clone());
+                        let size_bytes = dir_size(&path);
+
+                        manifest.add_model(InstalledModel {
+                            name: model_name,
+                            size_bytes,
+                            checksum: None,
+                            downloaded_at: chrono::Utc::now().to_rfc3339(),
+                            source,
+                        });
+                    }
+                }
+            } else if let Some(ext) = path.extension() {
                 if ext == "safetensors" {
                     if let Some(filename) = path.file_stem() {
                         if let Some(name_str) = filename.to_str() {
@@ -146,6 +232,7 @@ impl Manifest {
                                     size_bytes,
                                     checksum: None,
                                     downloaded_at: chrono::Utc::now().to_rfc3339(),
+                                    source: None,
                                 });
                             }
                         }
@@ -158,12 +245,36 @@ impl Manifest {
     }
 }
 
+/// Total size of the files in `dir`
+#[must_use]
+pub fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir).map_or(0, |entries| {
+        entries
+            .flatten()
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(fs::Metadata::is_file)
+            .map(|metadata| metadata.len())
+            .sum()
+    })
+}
+
 /// Get manifest path
 pub fn manifest_path() -> Result<PathBuf> {
     let data_dir = models_data_dir()?;
     Ok(data_dir.join("manifest.json"))
 }
 
+/// Get directory holding the files of an installed model
+pub fn model_dir(name: &str) -> Result<PathBuf> {
+    Ok(models_data_dir()?.join(model_dir_name(name)))
+}
+
+/// Directory name for a model, with `/` in `HuggingFace` names replaced by `--`
+#[must_use]
+pub fn model_dir_name(name: &str) -> String {
+    format!("whisper-{}", name.replace('/', "--"))
+}
+
 /// Get models data directory
 pub fn models_data_dir() -> Result<PathBuf> {
     let data_dir = if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
@@ -198,6 +309,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: Some("abc123".to_string()),
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -213,6 +325,7 @@ mod tests {
             size_bytes: 100,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model1);
@@ -223,6 +336,7 @@ mod tests {
             size_bytes: 200,
             checksum: Some("new".to_string()),
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model2);
@@ -238,6 +352,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -262,6 +377,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -284,6 +400,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -302,6 +419,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: None,
             downloaded_at: chrono::Utc::now().to_rfc3339(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -324,6 +442,7 @@ mod tests {
             size_bytes: 145_000_000,
             checksum: Some("abc123".to_string()),
             downloaded_at: "2026-01-01T00:00:00Z".to_string(),
+            source: None,
         };
 
         manifest.add_model(model);
@@ -371,4 +490,106 @@ mod tests {
         assert!(names.contains(&"tiny"));
         assert!(names.contains(&"base"));
     }
+
+    #[test]
+    fn test_regenerate_from_model_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path();
+
+        // Complete model directory
+        let small_dir = models_dir.join("whisper-small");
+        fs::create_dir(&small_dir).unwrap();
+        for file in [
+            ModelFiles::CONFIG,
+            ModelFiles::TOKENIZER,
+            ModelFiles::WEIGHTS,
+        ] {
+            fs::write(small_dir.join(file), "data").unwrap();
+        }
+
+        // Custom HuggingFace model
+        let custom_dir = models_dir.join(model_dir_name("acme/whisper-medical"));
+        fs::create_dir(&custom_dir).unwrap();
+        for file in [
+            ModelFiles::CONFIG,
+            ModelFiles::TOKENIZER,
+            ModelFiles::WEIGHTS,
+        ] {
+            fs::write(custom_dir.join(file), "data").unwrap();
+        }
+
+        // Incomplete download is not reported as installed
+        let medium_dir = models_dir.join("whisper-medium");
+        fs::create_dir(&medium_dir).unwrap();
+        fs::write(medium_dir.join(ModelFiles::CONFIG), "data").unwrap();
+
+        let manifest = Manifest::regenerate_from_disk(models_dir).unwrap();
+        assert_eq!(manifest.models.len(), 2);
+
+        let small = manifest.find_model("small").unwrap();
+        assert_eq!(small.size_bytes, 12);
+        assert!(small.source.is_none());
+
+        let custom = manifest.find_model("acme/whisper-medical").unwrap();
+        assert_eq!(custom.source.as_deref(), Some("acme/whisper-medical"));
+    }
+
+    #[test]
+    fn test_model_dir_name() {
+        assert_eq!(model_dir_name("base"), "whisper-base");
+        assert_eq!(
+            model_dir_name("acme/whisper-medical@refs/pr/3"),
+            "whisper-acme--whisper-medical@refs--pr--3"
+        );
+    }
+
+    #[test]
+    fn test_load_manifest_without_source() {
+        // Manifests written before custom models were supported have no source field
+        let temp_dir = TempDir::new().unwrap();
+        let manifest_path = temp_dir.path().join("manifest.json");
+        fs::write(
+            &manifest_path,
+            r#"{"models":[{"name":"base","size_bytes":1,"checksum":null,"downloaded_at":"2026-01-01T00:00:00Z"}],"active":"base","last_updated":"2026-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        let manifest = Manifest::load(&manifest_path).unwrap();
+        assert!(manifest.models[0].source.is_none());
+    }
+
+    #[test]
+    fn test_model_files_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = ModelFiles::in_dir(temp_dir.path());
+        assert_eq!(files.missing().len(), 3);
+
+        fs::write(&files.config, "{}").unwrap();
+        fs::write(&files.tokenizer, "{}").unwrap();
+        assert_eq!(files.missing(), vec![files.weights.as_path()]);
+
+        fs::write(&files.weights, "weights").unwrap();
+        assert!(files.missing().is_empty());
+        assert!(ModelFiles::complete_in(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_model_files_with_quantization() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = ModelFiles::in_dir(temp_dir.path());
+        assert_eq!(files.clone().with_quantization("none"), files);
+
+        let quantized = files.clone().with_quantization("q5_0");
+        assert_eq!(quantized.weights, temp_dir.path().join("model-q5_0.gguf"));
+        assert_eq!(quantized.config, files.config);
+
+        // Quantized weights alone make a complete model
+        fs::write(&files.config, "{}").unwrap();
+        fs::write(&files.tokenizer, "{}").unwrap();
+        assert!(!ModelFiles::complete_in(temp_dir.path()));
+        fs::write(&quantized.weights, "gguf").unwrap();
+        assert!(ModelFiles::complete_in(temp_dir.path()));
+        assert!(quantized.missing().is_empty());
+        assert_eq!(dir_size(temp_dir.path()), 8);
+    }
 }
@@ -1,9 +1,12 @@
 pub mod download;
 pub mod manager;
 pub mod manifest;
+pub mod quantize;
 pub mod registry;
+pub mod source;
 
 pub use download::ModelDownloader;
 pub use manager::ModelManager;
-pub use manifest::{InstalledModel, Manifest};
+pub use manifest::{InstalledModel, Manifest, ModelFiles};
 pub use registry::ModelInfo;
+pub use source::ModelSource;
@@ -0,0 +1,150 @@
+use crate::error::{Result, ScribeError, TranscriptionError};
+use candle_core::quantized::{gguf_file, GgmlDType, QTensor};
+use candle_core::safetensors::MmapedSafetensors;
+use candle_core::{DType, Device};
+use std::fs;
+use std::path::Path;
+
+/// Supported weight quantizations, from smallest to most accurate
+pub const QUANTIZATIONS: &[&str] = &["q4_0", "q5_0", "q8_0"];
+
+/// Map a quantization name to its GGML type
+pub fn ggml_dtype(quantization: &str) -> Result<GgmlDType> {
+    match quantization {
+        "q4_0" => Ok(GgmlDType::Q4_0),
+        "q5_0" => Ok(GgmlDType::Q5_0),
+        "q8_0" => Ok(GgmlDType::Q8_0),
+        _ => Err(ScribeError::Config(format!(
+            "Invalid quantization: '{quantization}'. Must be one of: {QUANTIZATIONS:?}"
+        ))),
+    }
+}
+
+/// Type a tensor of `shape` is stored as
+///
+/// Only matrices whose rows split into whole blocks are quantized; convolutions,
+/// biases and layer norms stay in f32.
+fn tensor_dtype(shape: &[usize], dtype: GgmlDType) -> GgmlDType {
+    match shape {
+        [_, cols] if cols % dtype.block_size() == 0 => dtype,
+        _ => GgmlDType::F32,
+    }
+}
+
+/// Convert safetensors weights into a quantized GGUF file
+///
+/// Tensors are read and quantized one at a time from the memory-mapped input,
+/// but every quantized tensor is kept until the GGUF file is written: peak memory
+/// is about the size of the quantized model plus one f32 tensor. Returns the size
+/// of the written file in bytes.
+pub fn quantize_weights(weights: &Path, output: &Path, dtype: GgmlDType) -> Result<u64> {
+    let model_error = |msg: String| ScribeError::Transcription(TranscriptionError::ModelError(msg));
+
+    // SAFETY: the weights file is not modified while mapped
+    let safetensors = unsafe { MmapedSafetensors::new(weights) }
+        .map_err(|e| model_error(format!("Failed to read model weights: {e}")))?;
+
+    let mut names: Vec<String> = safetensors.tensors().into_iter().map(|(n, _)| n).collect();
+    names.sort();
+
+    let mut tensors = Vec::with_capacity(names.len());
+    for name in &names {
+        let tensor = safetensors
+            .load(name, &Device::Cpu)
+            .and_then(|t| t.to_dtype(DType::F32))
+            .map_err(|e| model_error(format!("Failed to load tensor {name}: {e}")))?;
+
+        let qtensor = QTensor::quantize(&tensor, tensor_dtype(tensor.dims(), dtype))
+            .map_err(|e| model_error(format!("Failed to quantize tensor {name}: {e}")))?;
+        tensors.push((name.as_str(), qtensor));
+    }
+
+    // Write under a temporary name so an interrupted conversion is never loaded
+    let tmp_path = output.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    let tensor_refs: Vec<(&str, &QTensor)> = tensors.iter().map(|(n, t)| (*n, t)).collect();
+    gguf_file::write(&mut file, &[], &tensor_refs)
+        .map_err(|e| model_error(format!("Failed to write quantized weights: {e}")))?;
+    drop(file);
+    fs::rename(&tmp_path, output)?;
+
+    Ok(fs::metadata(output)?.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candle_core::Tensor;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_ggml_dtype() {
+        assert_eq!(ggml_dtype("q4_0").unwrap(), GgmlDType::Q4_0);
+        assert_eq!(ggml_dtype("q5_0").unwrap(), GgmlDType::Q5_0);
+        assert_eq!(ggml_dtype("q8_0").unwrap(), GgmlDType::Q8_0);
+        assert!(ggml_dtype("none").is_err());
+        assert!(ggml_dtype("q3_k").is_err());
+    }
+
+    #[test]
+    fn test_tensor_dtype() {
+        assert_eq!(tensor_dtype(&[384, 384], GgmlDType::Q8_0), GgmlDType::Q8_0);
+        assert_eq!(tensor_dtype(&[384], GgmlDType::Q8_0), GgmlDType::F32);
+        assert_eq!(tensor_dtype(&[384, 80, 3], GgmlDType::Q4_0), GgmlDType::F32);
+        assert_eq!(tensor_dtype(&[384, 50], GgmlDType::Q4_0), GgmlDType::F32);
+    }
+
+    #[test]
+    fn test_quantize_weights_roundtrip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let weights = temp_dir.path().join("model.safetensors");
+        let output = temp_dir.path().join("model-q8_0.gguf");
+
+        let matrix: Vec<f32> = (0u8..17)
+            .cycle()
+            .take(64 * 64)
+            .map(|i| f32::from(i) / 17.0)
+            .collect();
+        let tensors = HashMap::from([
+            (
+                "layer.weight".to_string(),
+                Tensor::from_vec(matrix.clone(), (64, 64), &Device::Cpu).unwrap(),
+            ),
+            (
+                "layer.bias".to_string(),
+                Tensor::from_vec(vec![0.5f32; 64], 64, &Device::Cpu).unwrap(),
+            ),
+        ]);
+        candle_core::safetensors::save(&tensors, &weights).unwrap();
+
+        let size = quantize_weights(&weights, &output, GgmlDType::Q8_0).unwrap();
+        assert!(size > 0);
+        assert!(!output.with_extension("tmp").exists());
+
+        let mut file = fs::File::open(&output).unwrap();
+        let content = gguf_file::Content::read(&mut file).unwrap();
+
+        let weight = content
+            .tensor(&mut file, "layer.weight", &Device::Cpu)
+            .unwrap();
+        assert_eq!(weight.dtype(), GgmlDType::Q8_0);
+        let restored: Vec<f32> = weight
+            .dequantize(&Device::Cpu)
+            .unwrap()
+            .flatten_all()
+            .unwrap()
+            .to_vec1()
+            .unwrap();
+        let max_error = restored
+            .iter()
+            .zip(&matrix)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_error < 0.01, "max error {max_error}");
+
+        let bias = content
+            .tensor(&mut file, "layer.bias", &Device::Cpu)
+            .unwrap();
+        assert_eq!(bias.dtype(), GgmlDType::F32);
+    }
+}
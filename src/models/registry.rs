@@ -1,3 +1,5 @@
+use crate::error::ScribeError;
+
 /// Information about a Whisper model
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModelInfo {
@@ -6,10 +8,29 @@ pub struct ModelInfo {
     pub parameters: &'static str,
     pub description: &'static str,
     pub recommended: bool,
+    /// Whether the model supports languages other than English (`.en` models do not)
+    pub multilingual: bool,
+    pub hf_repo: &'static str,
+    pub hf_revision: &'static str,
+    /// Published GGUF weights, downloaded instead of quantizing the f32 weights
+    pub quantized: &'static [QuantizedWeights],
+}
+
+/// Pre-quantized GGUF weights of a registry model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizedWeights {
+    /// Quantization as written in `transcription.quantization`
+    pub quantization: &'static str,
+    pub size_mb: u64,
     pub hf_repo: &'static str,
     pub hf_revision: &'static str,
+    /// GGUF file in `hf_repo`
+    pub file: &'static str,
 }
 
+/// Repo of candle's quantized Whisper checkpoints
+const CANDLE_WHISPER_REPO: &str = "lmz/candle-whisper";
+
 /// Registry of available Whisper models
 pub const MODELS: &[ModelInfo] = &[
     ModelInfo {
@@ -18,8 +39,16 @@ pub const MODELS: &[ModelInfo] = &[
         parameters: "39M",
         description: "Fastest, lowest accuracy",
         recommended: false,
+        multilingual: true,
         hf_repo: "openai/whisper-tiny",
         hf_revision: "main",
+        quantized: &[QuantizedWeights {
+            quantization: "q8_0",
+            size_mb: 42,
+            hf_repo: CANDLE_WHISPER_REPO,
+            hf_revision: "main",
+            file: "model-tiny-q80.gguf",
+        }],
     },
     ModelInfo {
         name: "base",
@@ -27,8 +56,10 @@ pub const MODELS: &[ModelInfo] = &[
         parameters: "74M",
         description: "Balanced for most use cases",
         recommended: true,
+        multilingual: true,
         hf_repo: "openai/whisper-base",
         hf_revision: "refs/pr/22",
+        quantized: &[],
     },
     ModelInfo {
         name: "small",
@@ -36,8 +67,10 @@ pub const MODELS: &[ModelInfo] = &[
         parameters: "244M",
         description: "Better accuracy, slower",
         recommended: false,
+        multilingual: true,
         hf_repo: "openai/whisper-small",
         hf_revision: "main",
+        quantized: &[],
     },
     ModelInfo {
         name: "medium",
@@ -45,8 +78,10 @@ pub const MODELS: &[ModelInfo] = &[
         parameters: "769M",
         description: "High accuracy, much slower",
         recommended: false,
+        multilingual: true,
         hf_repo: "openai/whisper-medium",
         hf_revision: "main",
+        quantized: &[],
     },
     ModelInfo {
         name: "large",
@@ -54,8 +89,104 @@ pub const MODELS: &[ModelInfo] = &[
         parameters: "1550M",
         description: "Best accuracy, very slow",
         recommended: false,
+        multilingual: true,
         hf_repo: "openai/whisper-large-v3",
         hf_revision: "main",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "tiny.en",
+        size_mb: 75,
+        parameters: "39M",
+        description: "English-only, fastest",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "openai/whisper-tiny.en",
+        hf_revision: "refs/pr/15",
+        quantized: &[QuantizedWeights {
+            quantization: "q8_0",
+            size_mb: 42,
+            hf_repo: CANDLE_WHISPER_REPO,
+            hf_revision: "main",
+            file: "model-tiny-en-q80.gguf",
+        }],
+    },
+    ModelInfo {
+        name: "base.en",
+        size_mb: 145,
+        parameters: "74M",
+        description: "English-only, balanced",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "openai/whisper-base.en",
+        hf_revision: "refs/pr/13",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "small.en",
+        size_mb: 466,
+        parameters: "244M",
+        description: "English-only, better accuracy",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "openai/whisper-small.en",
+        hf_revision: "refs/pr/10",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "medium.en",
+        size_mb: 1450,
+        parameters: "769M",
+        description: "English-only, high accuracy",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "openai/whisper-medium.en",
+        hf_revision: "main",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "large-v3-turbo",
+        size_mb: 1550,
+        parameters: "809M",
+        description: "Near-large accuracy, much faster decoding",
+        recommended: false,
+        multilingual: true,
+        hf_repo: "openai/whisper-large-v3-turbo",
+        hf_revision: "main",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "distil-small.en",
+        size_mb: 332,
+        parameters: "166M",
+        description: "Distilled, English-only, fast",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "distil-whisper/distil-small.en",
+        hf_revision: "main",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "distil-medium.en",
+        size_mb: 789,
+        parameters: "394M",
+        description: "Distilled, English-only, balanced",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "distil-whisper/distil-medium.en",
+        hf_revision: "main",
+        quantized: &[],
+    },
+    ModelInfo {
+        name: "distil-large-v3",
+        size_mb: 1510,
+        parameters: "756M",
+        description: "Distilled large-v3, English-only, fast with high accuracy",
+        recommended: false,
+        multilingual: false,
+        hf_repo: "distil-whisper/distil-large-v3",
+        hf_revision: "main",
+        quantized: &[],
     },
 ];
 
@@ -66,6 +197,14 @@ impl ModelInfo {
         MODELS.iter().find(|m| m.name == name)
     }
 
+    /// Published GGUF weights with `quantization`, if any
+    #[must_use]
+    pub fn quantized_weights(&self, quantization: &str) -> Option<&'static QuantizedWeights> {
+        self.quantized
+            .iter()
+            .find(|weights| weights.quantization == quantization)
+    }
+
     /// Get all model names
     #[must_use]
     pub fn all_names() -> Vec<&'static str> {
@@ -78,6 +217,21 @@ impl ModelInfo {
         MODELS.iter().find(|m| m.recommended).unwrap()
     }
 
+    /// Error for a model name that is not in the registry, with a suggestion
+    #[must_use]
+    pub fn unknown_model_error(name: &str) -> ScribeError {
+        let mut msg = format!("Unknown model: '{name}'");
+        if let Some(suggestion) = Self::suggest(name) {
+            msg.push_str("\n  Did you mean: ");
+            msg.push_str(suggestion);
+            msg.push('?');
+        }
+        msg.push_str("\n  Available: ");
+        msg.push_str(&Self::all_names().join(", "));
+        msg.push_str("\n  Custom models: owner/name[@revision] or a local directory path");
+        ScribeError::NotFound(msg)
+    }
+
     /// Find closest match using Levenshtein distance
     #[must_use]
     pub fn suggest(name: &str) -> Option<&'static str> {
@@ -134,6 +288,7 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::quantize::QUANTIZATIONS;
 
     #[test]
     fn test_find_model() {
@@ -146,12 +301,57 @@ mod tests {
     #[test]
     fn test_all_names() {
         let names = ModelInfo::all_names();
-        assert_eq!(names.len(), 5);
+        assert_eq!(names.len(), 13);
         assert!(names.contains(&"tiny"));
         assert!(names.contains(&"base"));
         assert!(names.contains(&"small"));
         assert!(names.contains(&"medium"));
         assert!(names.contains(&"large"));
+        assert!(names.contains(&"base.en"));
+        assert!(names.contains(&"large-v3-turbo"));
+        assert!(names.contains(&"distil-large-v3"));
+    }
+
+    #[test]
+    fn test_english_only_models() {
+        // distil-whisper models were distilled on English data only
+        for model in MODELS {
+            assert_eq!(
+                model.multilingual,
+                !(model.name.contains(".en") || model.name.starts_with("distil-")),
+                "{} multilingual flag",
+                model.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_quantized_weights() {
+        for model in MODELS {
+            for weights in model.quantized {
+                assert!(
+                    QUANTIZATIONS.contains(&weights.quantization),
+                    "{} {}",
+                    model.name,
+                    weights.quantization
+                );
+                assert!(std::path::Path::new(weights.file)
+                    .extension()
+                    .is_some_and(|ext| ext == "gguf"));
+                assert!(weights.size_mb < model.size_mb);
+            }
+        }
+
+        let tiny = ModelInfo::find("tiny").unwrap();
+        assert_eq!(
+            tiny.quantized_weights("q8_0").unwrap().file,
+            "model-tiny-q80.gguf"
+        );
+        assert!(tiny.quantized_weights("q4_0").is_none());
+        assert!(ModelInfo::find("small")
+            .unwrap()
+            .quantized_weights("q8_0")
+            .is_none());
     }
 
     #[test]
@@ -168,6 +368,7 @@ mod tests {
         assert_eq!(ModelInfo::suggest("tin"), Some("tiny"));
         assert_eq!(ModelInfo::suggest("smal"), Some("small"));
         assert_eq!(ModelInfo::suggest("larg"), Some("large"));
+        assert_eq!(ModelInfo::suggest("base.em"), Some("base.en"));
         assert_eq!(ModelInfo::suggest("invalid123"), None);
         assert_eq!(ModelInfo::suggest(""), None);
     }
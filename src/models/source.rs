@@ -0,0 +1,139 @@
+use crate::error::{Result, ScribeError};
+use crate::models::registry::ModelInfo;
+use std::path::PathBuf;
+
+/// Revision used when a `HuggingFace` repo is given without `@revision`
+const DEFAULT_REVISION: &str = "main";
+
+/// Where a Whisper model comes from, as written in `transcription.model`
+///
+/// - `base` - model from the built-in registry
+/// - `org/whisper-medical@v2` - `HuggingFace` repo, optionally pinned to a revision
+/// - `/models/whisper-medical` - local directory (must start with `/`, `./`, `../` or `~/`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelSource {
+    Registry(&'static ModelInfo),
+    HuggingFace { repo: String, revision: String },
+    Directory(PathBuf),
+}
+
+impl ModelSource {
+    /// Parse a model specification
+    pub fn parse(spec: &str) -> Result<Self> {
+        if let Some(rest) = spec.strip_prefix("~/") {
+            let home = std::env::var("HOME")
+                .map_err(|_| ScribeError::Config("HOME env var not set".to_string()))?;
+            return Ok(Self::Directory(PathBuf::from(home).join(rest)));
+        }
+
+        if spec.starts_with('/') || spec.starts_with("./") || spec.starts_with("../") {
+            return Ok(Self::Directory(PathBuf::from(spec)));
+        }
+
+        if let Some(info) = ModelInfo::find(spec) {
+            return Ok(Self::Registry(info));
+        }
+
+        if spec.contains('/') {
+            let (repo, revision) = spec.split_once('@').unwrap_or((spec, DEFAULT_REVISION));
+            let valid_repo = repo.split('/').count() == 2
+                && repo.split('/').all(|part| !part.is_empty())
+                && !repo.contains(char::is_whitespace);
+
+            if !valid_repo || revision.is_empty() {
+                return Err(ScribeError::Config(format!(
+                    "Invalid HuggingFace model: '{spec}'. Expected 'owner/name' or 'owner/name@revision'"
+                )));
+            }
+
+            return Ok(Self::HuggingFace {
+                repo: repo.to_string(),
+                revision: revision.to_string(),
+            });
+        }
+
+        Err(ModelInfo::unknown_model_error(spec))
+    }
+
+    /// Name the model is tracked under in the manifest
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::Registry(info) => info.name.to_string(),
+            Self::HuggingFace { repo, revision } if revision == DEFAULT_REVISION => repo.clone(),
+            Self::HuggingFace { repo, revision } => format!("{repo}@{revision}"),
+            Self::Directory(path) => path.display().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_registry() {
+        let source = ModelSource::parse("base").unwrap();
+        assert_eq!(
+            source,
+            ModelSource::Registry(ModelInfo::find("base").unwrap())
+        );
+        assert_eq!(source.name(), "base");
+    }
+
+    #[test]
+    fn test_parse_huggingface() {
+        let source = ModelSource::parse("acme/whisper-medical").unwrap();
+        assert_eq!(
+            source,
+            ModelSource::HuggingFace {
+                repo: "acme/whisper-medical".to_string(),
+                revision: "main".to_string(),
+            }
+        );
+        assert_eq!(source.name(), "acme/whisper-medical");
+
+        let source = ModelSource::parse("acme/whisper-medical@refs/pr/3").unwrap();
+        assert_eq!(
+            source,
+            ModelSource::HuggingFace {
+                repo: "acme/whisper-medical".to_string(),
+                revision: "refs/pr/3".to_string(),
+            }
+        );
+        assert_eq!(source.name(), "acme/whisper-medical@refs/pr/3");
+    }
+
+    #[test]
+    fn test_parse_invalid_huggingface() {
+        assert!(ModelSource::parse("acme/").is_err());
+        assert!(ModelSource::parse("acme/whisper/extra").is_err());
+        assert!(ModelSource::parse("acme/whisper@").is_err());
+        assert!(ModelSource::parse("acme/whisper medical").is_err());
+    }
+
+    #[test]
+    fn test_parse_directory() {
+        assert_eq!(
+            ModelSource::parse("/models/whisper-medical").unwrap(),
+            ModelSource::Directory(PathBuf::from("/models/whisper-medical"))
+        );
+        assert_eq!(
+            ModelSource::parse("./whisper").unwrap(),
+            ModelSource::Directory(PathBuf::from("./whisper"))
+        );
+
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(
+            ModelSource::parse("~/models/whisper").unwrap(),
+            ModelSource::Directory(PathBuf::from(home).join("models/whisper"))
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        let err = ModelSource::parse("basee").unwrap_err().to_string();
+        assert!(err.contains("Unknown model: 'basee'"));
+        assert!(err.contains("Did you mean: base?"));
+    }
+}
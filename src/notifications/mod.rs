@@ -1,4 +1,5 @@
 use crate::config::schema::NotificationConfig;
+use crate::error::{ScribeError, TranscriptionError};
 use notify_rust::{Notification, Timeout, Urgency};
 
 /// Desktop notification manager
@@ -110,6 +111,18 @@ impl NotificationManager {
             .ok();
     }
 
+    /// Show the notification matching an error from the daemon
+    ///
+    /// Quota and audio device errors get dedicated notifications; everything else
+    /// is reported as a transcription error using the first line of its message.
+    pub fn error(&self, error: &ScribeError) {
+        match error {
+            ScribeError::Transcription(TranscriptionError::QuotaExceeded) => self.error_api_quota(),
+            ScribeError::Audio(message) => self.error_audio_device(message),
+            other => self.error_transcription(&error_summary(other)),
+        }
+    }
+
     pub fn recording_cancelled(&self) {
         if !self.config.enable_status {
             return;
@@ -126,6 +139,16 @@ impl NotificationManager {
     }
 }
 
+/// First line of an error message, without the troubleshooting details
+fn error_summary(error: &ScribeError) -> String {
+    error
+        .to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +200,24 @@ mod tests {
         manager.error_api_quota();
         manager.error_transcription("test error");
         manager.error_audio_device("test device error");
+        manager.error(&ScribeError::Transcription(
+            TranscriptionError::QuotaExceeded,
+        ));
+        manager.error(&ScribeError::Audio("test device error".to_string()));
+        manager.error(&ScribeError::Injection("test injection error".to_string()));
+        manager.recording_cancelled();
+    }
+
+    #[test]
+    fn test_error_summary_strips_troubleshooting() {
+        let error = ScribeError::Transcription(TranscriptionError::ApiError("boom".to_string()));
+        assert_eq!(
+            error_summary(&error),
+            "Transcription error: API error: boom"
+        );
+
+        let error = ScribeError::Other("plain".to_string());
+        assert_eq!(error_summary(&error), "plain");
     }
 
     #[test]
@@ -0,0 +1,371 @@
+use crate::config::schema::{PhraseAction, PostprocessConfig};
+use crate::error::{Result, ScribeError};
+use crate::input::{Action, KeyCombo};
+
+/// Built-in phrases typing punctuation and line breaks
+const BUILTIN_TEXT: &[(&str, &str)] = &[
+    ("new line", "\n"),
+    ("newline", "\n"),
+    ("new paragraph", "\n\n"),
+    ("comma", ","),
+    ("period", "."),
+    ("full stop", "."),
+    ("question mark", "?"),
+    ("exclamation mark", "!"),
+    ("exclamation point", "!"),
+    ("colon", ":"),
+    ("semicolon", ";"),
+    ("open paren", "("),
+    ("close paren", ")"),
+];
+
+/// Built-in phrases pressing keys
+const BUILTIN_KEYS: &[(&str, &str)] = &[
+    ("select all", "ctrl+a"),
+    ("undo that", "ctrl+z"),
+    ("press enter", "enter"),
+    ("press tab", "tab"),
+    ("press escape", "esc"),
+];
+
+/// Built-in phrases running actions
+const BUILTIN_ACTIONS: &[(&str, &str)] = &[
+    ("delete that", "delete_last"),
+    ("scratch that", "delete_last"),
+];
+
+/// Actions available as `{ action = "..." }`
+const VALID_ACTIONS: &[&str] = &["delete_last"];
+
+/// Text made of these attaches to the preceding word
+const CLOSING: &[char] = &[',', '.', ';', ':', '!', '?', ')', ']', '}'];
+/// Punctuation Whisper may have added itself, replaced by spoken punctuation
+const SENTENCE_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?'];
+/// Text after which the next word follows without a space
+const OPENING: &[char] = &['\n', '(', '[', '{'];
+
+#[derive(Debug, Clone)]
+enum Command {
+    Text(String),
+    Key(KeyCombo),
+    DeleteLast,
+}
+
+/// Spoken commands recognized in transcribed text
+#[derive(Debug, Clone)]
+pub struct VoiceCommands {
+    /// Normalized phrase words and their commands, longest phrases first
+    phrases: Vec<(Vec<String>, Command)>,
+}
+
+impl VoiceCommands {
+    /// Build the phrase table from the built-in phrases and `[postprocess.phrases]`
+    ///
+    /// # Errors
+    /// - Returns error if a phrase is empty, or has an invalid key or action
+    pub fn from_config(config: &PostprocessConfig) -> Result<Self> {
+        let builtin = BUILTIN_TEXT
+            .iter()
+            .map(|&(phrase, text)| (phrase, PhraseAction::Text(text.to_string())))
+            .chain(BUILTIN_KEYS.iter().map(|&(phrase, key)| {
+                (
+                    phrase,
+                    PhraseAction::Key {
+                        key: key.to_string(),
+                    },
+                )
+            }))
+            .chain(BUILTIN_ACTIONS.iter().map(|&(phrase, action)| {
+                (
+                    phrase,
+                    PhraseAction::Action {
+                        action: action.to_string(),
+                    },
+                )
+            }));
+        let configured = config
+            .phrases
+            .iter()
+            .map(|(phrase, action)| (phrase.as_str(), action.clone()));
+
+        // Configured phrases come last and replace built-in ones
+        let mut phrases: Vec<(Vec<String>, Command)> = Vec::new();
+        for (phrase, action) in builtin.chain(configured) {
+            let words = normalize_phrase(phrase);
+            let command = command(phrase, &words, &action)?;
+            phrases.retain(|(existing, _)| *existing != words);
+            phrases.push((words, command));
+        }
+
+        // Prefer "new paragraph" over a shorter phrase that starts the same way
+        phrases.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+        Ok(Self { phrases })
+    }
+
+    /// Split `text` into typed text and the actions of spoken commands
+    #[must_use]
+    pub fn apply(&self, text: &str) -> Vec<Action> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let normalized: Vec<String> = words.iter().map(|word| normalize(word)).collect();
+
+        let mut output = Output::default();
+        let mut i = 0;
+        while i < words.len() {
+            if let Some((len, command)) = self.match_at(&normalized[i..]) {
+                output.command(command);
+                i += len;
+            } else {
+                output.word(words[i]);
+                i += 1;
+            }
+        }
+
+        output.finish(text.ends_with(char::is_whitespace))
+    }
+
+    fn match_at(&self, words: &[String]) -> Option<(usize, &Command)> {
+        self.phrases
+            .iter()
+            .find(|(phrase, _)| words.starts_with(phrase))
+            .map(|(phrase, command)| (phrase.len(), command))
+    }
+}
+
+fn command(phrase: &str, words: &[String], action: &PhraseAction) -> Result<Command> {
+    let invalid = |reason: String| {
+        ScribeError::Config(format!(
+            "Invalid phrase '{phrase}' in [postprocess.phrases]: {reason}"
+        ))
+    };
+
+    if words.is_empty() {
+        return Err(invalid("phrase has no words".to_string()));
+    }
+
+    match action {
+        PhraseAction::Text(text) => Ok(Command::Text(text.clone())),
+        PhraseAction::Key { key } => key.parse().map(Command::Key).map_err(invalid),
+        PhraseAction::Action { action } => match action.as_str() {
+            "delete_last" => Ok(Command::DeleteLast),
+            other => Err(invalid(format!(
+                "unknown action '{other}'. Must be one of: {VALID_ACTIONS:?}"
+            ))),
+        },
+    }
+}
+
+/// Lowercase `word` without the punctuation Whisper attaches to it
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn normalize_phrase(phrase: &str) -> Vec<String> {
+    phrase
+        .split_whitespace()
+        .map(normalize)
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Actions being built from transcribed words
+#[derive(Debug, Default)]
+struct Output {
+    actions: Vec<Action>,
+    /// Text not yet turned into a `Type` action
+    pending: String,
+    /// Whether the next word follows without a space
+    glue: bool,
+}
+
+impl Output {
+    fn word(&mut self, word: &str) {
+        if !self.pending.is_empty() && !self.glue {
+            self.pending.push(' ');
+        }
+        self.pending.push_str(word);
+        self.glue = false;
+    }
+
+    fn command(&mut self, command: &Command) {
+        match command {
+            Command::Text(text) => self.text(text),
+            Command::Key(combo) => {
+                self.flush();
+                self.actions.push(Action::Key(combo.clone()));
+                self.glue = false;
+            }
+            Command::DeleteLast => {
+                // Text of this transcription is dropped before it is typed
+                if self.pending.is_empty() {
+                    self.actions.push(Action::DeleteLast);
+                } else {
+                    self.pending.clear();
+                }
+                self.glue = false;
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let closing = !text.is_empty() && text.chars().all(|c| CLOSING.contains(&c));
+        if closing || text.starts_with('\n') {
+            let mut kept = self.pending.trim_end_matches(' ');
+            if closing && text.starts_with(SENTENCE_PUNCTUATION) {
+                kept = kept.trim_end_matches(SENTENCE_PUNCTUATION);
+            }
+            self.pending.truncate(kept.len());
+        } else if !self.pending.is_empty() && !self.glue {
+            self.pending.push(' ');
+        }
+
+        self.pending.push_str(text);
+        self.glue = text.ends_with(OPENING);
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            self.actions
+                .push(Action::Type(std::mem::take(&mut self.pending)));
+        }
+    }
+
+    /// Finish, keeping the trailing space that separates transcriptions
+    fn finish(mut self, trailing_space: bool) -> Vec<Action> {
+        if trailing_space && !self.pending.is_empty() && !self.glue {
+            self.pending.push(' ');
+        }
+        self.flush();
+        self.actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(phrases: &[(&str, PhraseAction)]) -> VoiceCommands {
+        let config = PostprocessConfig {
+            voice_commands: true,
+            phrases: phrases
+                .iter()
+                .map(|(phrase, action)| ((*phrase).to_string(), action.clone()))
+                .collect(),
+            replace: Vec::new(),
+        };
+        VoiceCommands::from_config(&config).unwrap()
+    }
+
+    fn typed(text: &str) -> Action {
+        Action::Type(text.to_string())
+    }
+
+    #[test]
+    fn test_punctuation() {
+        let commands = commands(&[]);
+
+        assert_eq!(
+            commands.apply("Hello comma world period "),
+            [typed("Hello, world. ")]
+        );
+        // Whisper's own punctuation gives way to the spoken one
+        assert_eq!(
+            commands.apply("Is it done, question mark? "),
+            [typed("Is it done? ")]
+        );
+        assert_eq!(
+            commands.apply("Call foo open paren bar close paren. "),
+            [typed("Call foo (bar) ")]
+        );
+        assert_eq!(
+            commands.apply("First line. New line. Second line. New paragraph. "),
+            [typed("First line.\nSecond line.\n\n")]
+        );
+        assert_eq!(
+            commands.apply("no commands here "),
+            [typed("no commands here ")]
+        );
+    }
+
+    #[test]
+    fn test_key_and_delete_actions() {
+        let commands = commands(&[]);
+
+        assert_eq!(
+            commands.apply("Select all. "),
+            [Action::Key("ctrl+a".parse().unwrap())]
+        );
+        assert_eq!(
+            commands.apply("hello there select all "),
+            [typed("hello there"), Action::Key("ctrl+a".parse().unwrap())]
+        );
+
+        // Deletes earlier text of the same transcription without typing it
+        assert_eq!(
+            commands.apply("wrong words delete that right words "),
+            [typed("right words ")]
+        );
+        // Otherwise deletes the previous transcription
+        assert_eq!(commands.apply("Delete that. "), [Action::DeleteLast]);
+    }
+
+    #[test]
+    fn test_configured_phrases() {
+        let commands = commands(&[
+            ("smiley face", PhraseAction::Text(":-)".to_string())),
+            (
+                "save the file",
+                PhraseAction::Key {
+                    key: "ctrl+s".to_string(),
+                },
+            ),
+            // Mapping a phrase to itself turns a built-in command off
+            ("period", PhraseAction::Text("period".to_string())),
+        ]);
+
+        assert_eq!(commands.apply("Nice smiley face. "), [typed("Nice :-) ")]);
+        assert_eq!(
+            commands.apply("Save the file. "),
+            [Action::Key("ctrl+s".parse().unwrap())]
+        );
+        assert_eq!(
+            commands.apply("a period of time "),
+            [typed("a period of time ")]
+        );
+    }
+
+    #[test]
+    fn test_invalid_phrases() {
+        let config = |phrase: &str, action: PhraseAction| PostprocessConfig {
+            voice_commands: true,
+            phrases: std::iter::once((phrase.to_string(), action)).collect(),
+            replace: Vec::new(),
+        };
+
+        assert!(VoiceCommands::from_config(&config(
+            "open tab",
+            PhraseAction::Key {
+                key: "ctrl+t".to_string()
+            }
+        ))
+        .is_ok());
+        assert!(VoiceCommands::from_config(&config(
+            "open tab",
+            PhraseAction::Key {
+                key: "hyper+t".to_string()
+            }
+        ))
+        .is_err());
+        assert!(VoiceCommands::from_config(&config(
+            "nuke it",
+            PhraseAction::Action {
+                action: "format_disk".to_string()
+            }
+        ))
+        .is_err());
+        assert!(
+            VoiceCommands::from_config(&config("...", PhraseAction::Text("x".to_string())))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Text processing between transcription and injection
+//!
+//! Configured in `[postprocess]`: replacement rules rewrite the transcribed
+//! text, then voice commands turn it into the [`Action`]s the text injector
+//! performs.
+
+pub mod commands;
+pub mod replace;
+
+use crate::config::schema::PostprocessConfig;
+use crate::error::Result;
+use crate::input::Action;
+
+pub use commands::VoiceCommands;
+pub use replace::Replacements;
+
+/// Post-processing steps enabled in the config
+#[derive(Debug, Clone, Default)]
+pub struct PostProcessor {
+    replacements: Replacements,
+    commands: Option<VoiceCommands>,
+}
+
+impl PostProcessor {
+    /// Build the enabled steps
+    ///
+    /// # Errors
+    /// - Returns error if a replacement pattern is an invalid regular expression
+    /// - Returns error if a configured phrase or key combination is invalid
+    pub fn from_config(config: &PostprocessConfig) -> Result<Self> {
+        // Phrases are checked even while voice commands are off
+        let commands = VoiceCommands::from_config(config)?;
+
+        Ok(Self {
+            replacements: Replacements::new(&config.replace)?,
+            commands: config.voice_commands.then_some(commands),
+        })
+    }
+
+    /// Apply the replacement rules to transcribed `text`
+    #[must_use]
+    pub fn rewrite(&self, text: &str) -> String {
+        self.replacements.apply(text)
+    }
+
+    /// Turn rewritten `text` into actions for the injector
+    #[must_use]
+    pub fn actions(&self, text: &str) -> Vec<Action> {
+        self.commands.as_ref().map_or_else(
+            || vec![Action::Type(text.to_string())],
+            |commands| commands.apply(text),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::ReplaceRule;
+
+    #[test]
+    fn test_replacements_before_commands() {
+        let config = PostprocessConfig {
+            voice_commands: true,
+            phrases: std::collections::BTreeMap::new(),
+            replace: vec![ReplaceRule {
+                pattern: "new lion".to_string(),
+                replacement: "new line".to_string(),
+                regex: false,
+                ignore_case: true,
+                whole_word: true,
+            }],
+        };
+        let postprocessor = PostProcessor::from_config(&config).unwrap();
+
+        // A mis-heard command is fixed before commands are recognized
+        let text = postprocessor.rewrite("Done. New lion. ");
+        assert_eq!(text, "Done. new line. ");
+        assert_eq!(
+            postprocessor.actions(&text),
+            [Action::Type("Done.\n".to_string())]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::config::schema::ReplaceRule;
+use crate::error::{Result, ScribeError};
+use regex::{NoExpand, Regex, RegexBuilder};
+
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    replacement: String,
+    /// Whether `$` in the replacement refers to capture groups
+    expand: bool,
+}
+
+/// Compiled `[[postprocess.replace]]` rules
+#[derive(Debug, Clone, Default)]
+pub struct Replacements {
+    rules: Vec<Rule>,
+}
+
+impl Replacements {
+    /// Compile `rules`
+    ///
+    /// # Errors
+    /// - Returns error if a pattern is empty or an invalid regular expression
+    pub fn new(rules: &[ReplaceRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let invalid = |reason: String| {
+                    ScribeError::Config(format!(
+                        "Invalid [[postprocess.replace]] pattern '{}': {reason}",
+                        rule.pattern
+                    ))
+                };
+
+                if rule.pattern.is_empty() {
+                    return Err(invalid("pattern is empty".to_string()));
+                }
+
+                let pattern = if rule.regex {
+                    rule.pattern.clone()
+                } else {
+                    regex::escape(&rule.pattern)
+                };
+                let pattern = if rule.whole_word {
+                    format!(r"\b(?:{pattern})\b")
+                } else {
+                    pattern
+                };
+
+                let regex = RegexBuilder::new(&pattern)
+                    .case_insensitive(rule.ignore_case)
+                    .build()
+                    .map_err(|e| invalid(e.to_string()))?;
+
+                Ok(Rule {
+                    regex,
+                    replacement: rule.replacement.clone(),
+                    expand: rule.regex,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Apply every rule to `text`, in order
+    #[must_use]
+    pub fn apply(&self, text: &str) -> String {
+        self.rules.iter().fold(text.to_string(), |text, rule| {
+            if rule.expand {
+                rule.regex.replace_all(&text, rule.replacement.as_str())
+            } else {
+                rule.regex.replace_all(&text, NoExpand(&rule.replacement))
+            }
+            .into_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, replacement: &str) -> ReplaceRule {
+        ReplaceRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            regex: false,
+            ignore_case: false,
+            whole_word: false,
+        }
+    }
+
+    #[test]
+    fn test_literal_rules() {
+        let replacements = Replacements::new(&[
+            ReplaceRule {
+                ignore_case: true,
+                whole_word: true,
+                ..rule("scribe", "Scribe")
+            },
+            // Regex syntax and '$' are literal in literal rules
+            rule("C++ (beta)", "$cpp"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            replacements.apply("SCRIBE and scribe, not scribed. "),
+            "Scribe and Scribe, not scribed. "
+        );
+        assert_eq!(replacements.apply("C++ (beta) "), "$cpp ");
+        assert_eq!(replacements.apply("C+ (beta)"), "C+ (beta)");
+    }
+
+    #[test]
+    fn test_regex_rules() {
+        let replacements = Replacements::new(&[
+            ReplaceRule {
+                regex: true,
+                ignore_case: true,
+                ..rule(r"open\s?ai", "OpenAI")
+            },
+            ReplaceRule {
+                regex: true,
+                whole_word: true,
+                ..rule(r"(\d+) percent", "$1%")
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(
+            replacements.apply("Open AI and openai grew 20 percent"),
+            "OpenAI and OpenAI grew 20%"
+        );
+        // Case-sensitive unless ignore_case is set
+        assert_eq!(replacements.apply("20 Percent"), "20 Percent");
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let replacements =
+            Replacements::new(&[rule("Whisker", "Whisper"), rule("Whisper", "whisper.cpp")])
+                .unwrap();
+        assert_eq!(replacements.apply("Whisker"), "whisper.cpp");
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let invalid = ReplaceRule {
+            regex: true,
+            ..rule("(unclosed", "x")
+        };
+        let error = Replacements::new(&[invalid]).unwrap_err();
+        assert!(error.to_string().contains("(unclosed"));
+
+        assert!(Replacements::new(&[rule("", "x")]).is_err());
+        // The same pattern is fine as a literal
+        assert!(Replacements::new(&[rule("(unclosed", "x")]).is_ok());
+    }
+}
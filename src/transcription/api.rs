@@ -1,25 +1,64 @@
 use crate::config::schema::TranscriptionConfig;
 use crate::error::{Result, ScribeError, TranscriptionError};
-use crate::transcription::TranscriptionBackend;
+use crate::transcription::{Task, TranscriptionBackend, SAMPLE_RATE};
 use async_trait::async_trait;
 use reqwest::StatusCode;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
 use std::time::Duration;
 
+/// Base URL of the `OpenAI` API
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Upper bound for the delay between retries
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Server an [`OpenAIBackend`] talks to
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    /// Backend name reported in logs
+    pub name: &'static str,
+    /// API root, e.g. `https://api.openai.com/v1`
+    pub base_url: String,
+    /// Environment variable containing the API key
+    pub api_key_env: String,
+    pub model: String,
+    /// Fail at startup when the key is missing
+    pub require_key: bool,
+}
+
 /// `OpenAI` API transcription backend
+///
+/// Works with any server implementing the `OpenAI` audio API, such as
+/// faster-whisper-server, `LocalAI` or the whisper.cpp server.
 pub struct OpenAIBackend {
     client: reqwest::Client,
-    api_key: String,
+    name: &'static str,
+    /// Bearer token; self-hosted servers may run without one
+    api_key: Option<String>,
     model: String,
+    base_url: String,
+    /// Language of the audio, empty to let the server detect it
+    language: String,
+    prompt: Option<String>,
+    temperature: f32,
     timeout: Duration,
+    retry: RetryPolicy,
 }
 
 impl std::fmt::Debug for OpenAIBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OpenAIBackend")
             .field("client", &"Client { ... }")
-            .field("api_key", &"***")
+            .field("name", &self.name)
+            .field("api_key", &self.api_key.as_ref().map(|_| "***"))
             .field("model", &self.model)
+            .field("base_url", &self.base_url)
+            .field("language", &self.language)
+            .field("prompt", &self.prompt)
+            .field("temperature", &self.temperature)
             .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
             .finish()
     }
 }
@@ -27,167 +66,306 @@ impl std::fmt::Debug for OpenAIBackend {
 impl OpenAIBackend {
     /// Create new `OpenAI` backend from config
     pub fn new(config: &TranscriptionConfig) -> Result<Self> {
-        // Read API key from environment variable
-        let api_key_env = config.api_key_env.as_deref().unwrap_or("OPENAI_API_KEY");
-
-        let api_key = std::env::var(api_key_env)
-            .map_err(|_| ScribeError::Transcription(TranscriptionError::InvalidApiKey))?;
+        let base_url = config
+            .api_base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        // OpenAI requires a key, other servers decide for themselves
+        let require_key = base_url == DEFAULT_BASE_URL;
+
+        Self::with_endpoint(
+            config,
+            Endpoint {
+                name: "openai",
+                base_url,
+                api_key_env: config
+                    .api_key_env
+                    .clone()
+                    .unwrap_or_else(|| "OPENAI_API_KEY".to_string()),
+                model: config
+                    .api_model
+                    .clone()
+                    .unwrap_or_else(|| "whisper-1".to_string()),
+                require_key,
+            },
+        )
+    }
 
-        if api_key.is_empty() {
+    /// Create a backend for an OpenAI-compatible `endpoint`
+    ///
+    /// Language, prompt, timeout and retry settings are taken from `config`.
+    pub fn with_endpoint(config: &TranscriptionConfig, endpoint: Endpoint) -> Result<Self> {
+        let api_key = api_key(&endpoint.api_key_env);
+        if api_key.is_none() && endpoint.require_key {
             return Err(ScribeError::Transcription(
                 TranscriptionError::InvalidApiKey,
             ));
         }
 
-        let model = config
-            .api_model
-            .clone()
-            .unwrap_or_else(|| "whisper-1".to_string());
-
-        let timeout_secs = config.api_timeout_secs.unwrap_or(30);
-
         Ok(Self {
             client: reqwest::Client::new(),
+            name: endpoint.name,
             api_key,
-            model,
-            timeout: Duration::from_secs(timeout_secs),
+            model: endpoint.model,
+            base_url: endpoint.base_url.trim_end_matches('/').to_string(),
+            language: config.language.clone(),
+            prompt: config
+                .initial_prompt
+                .as_deref()
+                .map(str::trim)
+                .filter(|prompt| !prompt.is_empty())
+                .map(str::to_string),
+            temperature: config.api_temperature.unwrap_or(0.0),
+            timeout: request_timeout(config),
+            retry: RetryPolicy::from_config(config),
         })
     }
 
-    /// Convert i16 audio samples to WAV bytes
-    fn audio_to_wav(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>> {
-        let mut cursor = std::io::Cursor::new(Vec::new());
-
-        let spec = hound::WavSpec {
-            channels: 1,
-            sample_rate,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
+    /// Endpoint for `task`; translations always produce English
+    fn endpoint(base_url: &str, task: Task) -> String {
+        match task {
+            Task::Transcribe => format!("{base_url}/audio/transcriptions"),
+            Task::Translate => format!("{base_url}/audio/translations"),
+        }
+    }
 
-        let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ApiError(format!(
-                "Failed to create WAV writer: {e}"
-            )))
-        })?;
+    /// Build the multipart request body for `task`
+    fn form(&self, wav_bytes: Vec<u8>, task: Task) -> Result<reqwest::multipart::Form> {
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", wav_part(wav_bytes)?)
+            .text("model", self.model.clone())
+            .text("temperature", self.temperature.to_string());
 
-        for &sample in samples {
-            writer.write_sample(sample).map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ApiError(format!(
-                    "Failed to write audio sample: {e}"
-                )))
-            })?;
+        // Translations are always English, so the endpoint takes no language
+        if task == Task::Transcribe && !self.language.is_empty() {
+            form = form.text("language", self.language.clone());
+        }
+        if let Some(prompt) = &self.prompt {
+            form = form.text("prompt", prompt.clone());
         }
 
-        writer.finalize().map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ApiError(format!(
-                "Failed to finalize WAV file: {e}"
-            )))
-        })?;
-
-        Ok(cursor.into_inner())
+        Ok(form)
     }
 
-    /// Post-process API response
-    fn post_process(text: &str) -> String {
-        let trimmed = text.trim();
-        if trimmed.is_empty() {
-            String::new()
-        } else {
-            format!("{trimmed} ")
+    /// Send one transcription request
+    async fn request(&self, wav_bytes: &[u8], task: Task) -> Result<String> {
+        let form = self.form(wav_bytes.to_vec(), task)?;
+
+        let mut request = self.client.post(Self::endpoint(&self.base_url, task));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
         }
+        let json = send(request.multipart(form).timeout(self.timeout)).await?;
+
+        let text = json["text"].as_str().ok_or_else(|| {
+            ScribeError::Transcription(TranscriptionError::ApiError(
+                "Missing 'text' field in API response".to_string(),
+            ))
+        })?;
+
+        Ok(post_process(text))
     }
 }
 
 #[async_trait]
 impl TranscriptionBackend for OpenAIBackend {
-    async fn transcribe(&self, audio: &[i16]) -> Result<String> {
-        // Convert audio to WAV format
-        let wav_bytes = Self::audio_to_wav(audio, 16000)?;
-
-        // Create multipart form
-        let file_part = reqwest::multipart::Part::bytes(wav_bytes)
-            .file_name("audio.wav")
-            .mime_str("audio/wav")
-            .map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ApiError(format!(
-                    "Failed to set MIME type: {e}"
-                )))
-            })?;
-
-        let form = reqwest::multipart::Form::new()
-            .part("file", file_part)
-            .text("model", self.model.clone());
-
-        // Send request to OpenAI API
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/audio/transcriptions")
-            .bearer_auth(&self.api_key)
-            .multipart(form)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::NetworkError(e.to_string()))
-            })?;
-
-        // Handle response
-        let status = response.status();
-
-        match status {
-            StatusCode::OK => {
-                let json: serde_json::Value = response.json().await.map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ApiError(format!(
-                        "Failed to parse response: {e}"
-                    )))
-                })?;
-
-                let text = json["text"]
-                    .as_str()
-                    .ok_or_else(|| {
-                        ScribeError::Transcription(TranscriptionError::ApiError(
-                            "Missing 'text' field in API response".to_string(),
-                        ))
-                    })?
-                    .to_string();
-
-                Ok(Self::post_process(&text))
-            }
-            StatusCode::TOO_MANY_REQUESTS => Err(ScribeError::Transcription(
-                TranscriptionError::QuotaExceeded,
-            )),
-            StatusCode::UNAUTHORIZED => Err(ScribeError::Transcription(
-                TranscriptionError::InvalidApiKey,
-            )),
-            StatusCode::BAD_REQUEST => {
-                let error_body = response.text().await.unwrap_or_default();
-                Err(ScribeError::Transcription(TranscriptionError::ApiError(
-                    format!("Bad request: {error_body}"),
-                )))
-            }
-            _ => {
-                let error_body = response.text().await.unwrap_or_default();
-                Err(ScribeError::Transcription(TranscriptionError::ApiError(
-                    format!("API error ({status}): {error_body}"),
-                )))
+    async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String> {
+        let wav_bytes = audio_to_wav(audio, SAMPLE_RATE)?;
+        self.retry.run(|| self.request(&wav_bytes, task)).await
+    }
+
+    fn backend_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// Retries for remote requests that fail with transient errors
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    max_retries: u32,
+    /// Delay before the first retry, doubled for each further one
+    delay: Duration,
+}
+
+impl RetryPolicy {
+    pub(crate) fn from_config(config: &TranscriptionConfig) -> Self {
+        Self {
+            max_retries: config.api_max_retries.unwrap_or(2),
+            delay: Duration::from_millis(config.api_retry_delay_ms.unwrap_or(500)),
+        }
+    }
+
+    /// Run `request`, retrying network errors, rate limits and server errors
+    pub(crate) async fn run<T, F, Fut>(&self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(ScribeError::Transcription(e))
+                    if e.is_transient() && attempt < self.max_retries =>
+                {
+                    let delay = self.backoff_delay(attempt, Self::jitter());
+                    attempt += 1;
+                    tracing::warn!(
+                        error = %e,
+                        attempt,
+                        max_retries = self.max_retries,
+                        delay_ms = delay.as_millis(),
+                        "API request failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
             }
         }
     }
 
-    fn backend_name(&self) -> &'static str {
-        "openai"
+    /// Delay before retry number `attempt` (0-based): exponential backoff with jitter
+    ///
+    /// `jitter` in `[0, 1)` picks a point in the upper half of the exponential
+    /// delay, so concurrent clients spread out without retrying too early.
+    fn backoff_delay(&self, attempt: u32, jitter: f64) -> Duration {
+        let delay = self
+            .delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RETRY_DELAY);
+        delay / 2 + (delay / 2).mul_f64(jitter)
+    }
+
+    /// Random jitter in `[0, 1)`
+    #[allow(clippy::cast_precision_loss)]
+    fn jitter() -> f64 {
+        // RandomState is freshly keyed per call; enough randomness for spreading retries
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        (random >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Read a non-empty API key from environment variable `name`
+pub(crate) fn api_key(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|key| !key.is_empty())
+}
+
+/// Per-request timeout from `api_timeout_secs`
+pub(crate) fn request_timeout(config: &TranscriptionConfig) -> Duration {
+    Duration::from_secs(config.api_timeout_secs.unwrap_or(30))
+}
+
+/// Convert i16 audio samples to WAV bytes
+pub(crate) fn audio_to_wav(samples: &[i16], sample_rate: u32) -> Result<Vec<u8>> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(|e| {
+        ScribeError::Transcription(TranscriptionError::ApiError(format!(
+            "Failed to create WAV writer: {e}"
+        )))
+    })?;
+
+    for &sample in samples {
+        writer.write_sample(sample).map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ApiError(format!(
+                "Failed to write audio sample: {e}"
+            )))
+        })?;
+    }
+
+    writer.finalize().map_err(|e| {
+        ScribeError::Transcription(TranscriptionError::ApiError(format!(
+            "Failed to finalize WAV file: {e}"
+        )))
+    })?;
+
+    Ok(cursor.into_inner())
+}
+
+/// Multipart file part holding WAV audio
+pub(crate) fn wav_part(wav_bytes: Vec<u8>) -> Result<reqwest::multipart::Part> {
+    reqwest::multipart::Part::bytes(wav_bytes)
+        .file_name("audio.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ApiError(format!(
+                "Failed to set MIME type: {e}"
+            )))
+        })
+}
+
+/// Send `request` and parse its JSON response, mapping HTTP errors
+pub(crate) async fn send(request: reqwest::RequestBuilder) -> Result<serde_json::Value> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ScribeError::Transcription(TranscriptionError::NetworkError(e.to_string())))?;
+
+    let status = response.status();
+
+    match status {
+        StatusCode::OK => response.json().await.map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ApiError(format!(
+                "Failed to parse response: {e}"
+            )))
+        }),
+        StatusCode::TOO_MANY_REQUESTS => Err(ScribeError::Transcription(
+            TranscriptionError::QuotaExceeded,
+        )),
+        StatusCode::UNAUTHORIZED => Err(ScribeError::Transcription(
+            TranscriptionError::InvalidApiKey,
+        )),
+        StatusCode::BAD_REQUEST => {
+            let error_body = response.text().await.unwrap_or_default();
+            Err(ScribeError::Transcription(TranscriptionError::ApiError(
+                format!("Bad request: {error_body}"),
+            )))
+        }
+        status if status.is_server_error() => {
+            let error_body = response.text().await.unwrap_or_default();
+            Err(ScribeError::Transcription(TranscriptionError::ServerError(
+                format!("{status}: {error_body}"),
+            )))
+        }
+        _ => {
+            let error_body = response.text().await.unwrap_or_default();
+            Err(ScribeError::Transcription(TranscriptionError::ApiError(
+                format!("API error ({status}): {error_body}"),
+            )))
+        }
+    }
+}
+
+/// Post-process API response
+pub(crate) fn post_process(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{trimmed} ")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::schema::Config;
 
     #[test]
     fn test_audio_to_wav() {
         let samples = vec![0i16, 1000, -1000, 5000, -5000];
-        let wav_bytes = OpenAIBackend::audio_to_wav(&samples, 16000).unwrap();
+        let wav_bytes = audio_to_wav(&samples, 16000).unwrap();
 
         // Verify WAV header exists (RIFF magic number)
         assert_eq!(&wav_bytes[0..4], b"RIFF");
@@ -203,21 +381,45 @@ mod tests {
 
         // Test different sample rates
         for &rate in &[8000u32, 16000, 48000] {
-            let result = OpenAIBackend::audio_to_wav(&samples, rate);
+            let result = audio_to_wav(&samples, rate);
             assert!(result.is_ok());
         }
     }
 
     #[test]
-    fn test_post_process() {
-        assert_eq!(OpenAIBackend::post_process("hello"), "hello ");
+    fn test_endpoint() {
         assert_eq!(
-            OpenAIBackend::post_process("  hello world  "),
-            "hello world "
+            OpenAIBackend::endpoint(DEFAULT_BASE_URL, Task::Transcribe),
+            "https://api.openai.com/v1/audio/transcriptions"
+        );
+        assert_eq!(
+            OpenAIBackend::endpoint("http://localhost:8000/v1", Task::Translate),
+            "http://localhost:8000/v1/audio/translations"
         );
-        assert_eq!(OpenAIBackend::post_process("test."), "test. ");
-        assert_eq!(OpenAIBackend::post_process(""), String::new());
-        assert_eq!(OpenAIBackend::post_process("   "), String::new());
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let retry = RetryPolicy {
+            max_retries: 2,
+            delay: Duration::from_millis(500),
+        };
+        assert_eq!(retry.backoff_delay(0, 0.0), Duration::from_millis(250));
+        assert_eq!(retry.backoff_delay(2, 0.5), Duration::from_millis(1500));
+        assert!(retry.backoff_delay(3, 0.999) < Duration::from_secs(4));
+        assert!(retry.backoff_delay(40, 0.999) <= MAX_RETRY_DELAY);
+
+        let jitter = RetryPolicy::jitter();
+        assert!((0.0..1.0).contains(&jitter));
+    }
+
+    #[test]
+    fn test_post_process() {
+        assert_eq!(post_process("hello"), "hello ");
+        assert_eq!(post_process("  hello world  "), "hello world ");
+        assert_eq!(post_process("test."), "test. ");
+        assert_eq!(post_process(""), String::new());
+        assert_eq!(post_process("   "), String::new());
     }
 
     #[test]
@@ -226,16 +428,9 @@ mod tests {
         let original = std::env::var("OPENAI_API_KEY_TEST").ok();
         std::env::remove_var("OPENAI_API_KEY_TEST");
 
-        let config = TranscriptionConfig {
-            backend: "openai".to_string(),
-            model: "base".to_string(),
-            device: "cpu".to_string(),
-            language: "en".to_string(),
-            initial_prompt: None,
-            api_key_env: Some("OPENAI_API_KEY_TEST".to_string()),
-            api_model: Some("whisper-1".to_string()),
-            api_timeout_secs: Some(30),
-        };
+        let mut config = Config::default().transcription;
+        config.backend = "openai".to_string();
+        config.api_key_env = Some("OPENAI_API_KEY_TEST".to_string());
 
         let result = OpenAIBackend::new(&config);
         assert!(result.is_err());
@@ -259,16 +454,9 @@ mod tests {
         let original = std::env::var("OPENAI_API_KEY_TEST").ok();
         std::env::set_var("OPENAI_API_KEY_TEST", "");
 
-        let config = TranscriptionConfig {
-            backend: "openai".to_string(),
-            model: "base".to_string(),
-            device: "cpu".to_string(),
-            language: "en".to_string(),
-            initial_prompt: None,
-            api_key_env: Some("OPENAI_API_KEY_TEST".to_string()),
-            api_model: Some("whisper-1".to_string()),
-            api_timeout_secs: Some(30),
-        };
+        let mut config = Config::default().transcription;
+        config.backend = "openai".to_string();
+        config.api_key_env = Some("OPENAI_API_KEY_TEST".to_string());
 
         let result = OpenAIBackend::new(&config);
         assert!(result.is_err());
@@ -0,0 +1,806 @@
+use crate::config::schema::TranscriptionConfig;
+use crate::error::{Result, ScribeError, TranscriptionError};
+use candle_core::{Device, Tensor};
+use candle_transformers::generation::LogitsProcessor;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// Seed for temperature sampling, fixed so the same audio decodes the same way
+const SAMPLING_SEED: u64 = 299_792_458;
+
+/// Highest temperature tried by the fallback
+const MAX_TEMPERATURE: f32 = 1.0;
+
+/// Seconds between consecutive timestamp tokens
+const TIMESTAMP_STEP: f64 = 0.02;
+
+/// Latest timestamp token allowed to open a window (1 second)
+const MAX_INITIAL_TIMESTAMP: usize = 50;
+
+/// Decoding strategy and fallback thresholds for the local backend
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodingOptions {
+    /// Beams kept during beam search (1 = greedy)
+    pub beam_size: usize,
+    /// Finished candidates collected before beam search stops, relative to `beam_size`
+    pub patience: f32,
+    /// Temperatures tried in order until a decode passes the thresholds
+    pub temperatures: Vec<f32>,
+    /// Retry when the average token log-probability is below this
+    pub logprob_threshold: f32,
+    /// Retry when the text compresses better than this (repetition loops)
+    pub compression_ratio_threshold: f32,
+    /// Treat a segment as silence above this no-speech probability
+    pub no_speech_threshold: f32,
+    /// Predict timestamp tokens instead of forcing `<|notimestamps|>`
+    pub timestamps: bool,
+}
+
+impl DecodingOptions {
+    /// Build decoding options from `[transcription]` settings
+    #[must_use]
+    pub fn from_config(config: &TranscriptionConfig) -> Self {
+        Self {
+            beam_size: config.beam_size,
+            patience: config.patience,
+            temperatures: fallback_temperatures(config.temperature_increment),
+            logprob_threshold: config.logprob_threshold,
+            compression_ratio_threshold: config.compression_ratio_threshold,
+            no_speech_threshold: config.no_speech_threshold,
+            timestamps: config.timestamps,
+        }
+    }
+
+    /// Whether a decode is poor enough to retry at a higher temperature
+    ///
+    /// Silence is not retried: sampling would only produce more hallucinations.
+    fn needs_fallback(&self, result: &DecodingResult) -> bool {
+        if self.is_silence(result) {
+            return false;
+        }
+
+        result.compression_ratio > self.compression_ratio_threshold
+            || result.avg_logprob < self.logprob_threshold
+    }
+
+    /// Whether a segment is most likely silence
+    ///
+    /// Unlike Whisper, confidence does not matter: hallucinations on silence
+    /// such as "Thank you." are often decoded confidently.
+    fn is_silence(&self, result: &DecodingResult) -> bool {
+        result.no_speech_prob > self.no_speech_threshold
+    }
+
+    /// Number of finished candidates beam search collects before stopping
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn max_candidates(&self) -> usize {
+        ((self.beam_size as f32 * self.patience).round() as usize).max(1)
+    }
+}
+
+/// Temperatures from 0.0 up to 1.0 in steps of `increment` (0 disables fallback)
+fn fallback_temperatures(increment: f32) -> Vec<f32> {
+    if increment <= 0.0 {
+        return vec![0.0];
+    }
+
+    std::iter::successors(Some(0.0f32), |t| Some(t + increment))
+        .take_while(|t| *t <= MAX_TEMPERATURE + 1e-4)
+        .collect()
+}
+
+/// Output of decoding a single segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodingResult {
+    /// Generated tokens: text and, with timestamp decoding, timestamp tokens
+    /// (other special tokens excluded)
+    pub tokens: Vec<u32>,
+    /// Log-probability of each token in `tokens`
+    pub token_logprobs: Vec<f32>,
+    pub text: String,
+    /// Average log-probability per token, counting a sampled end-of-text token
+    pub avg_logprob: f32,
+    /// Ratio of text bytes to zlib-compressed bytes
+    pub compression_ratio: f32,
+    /// Temperature of the accepted attempt
+    pub temperature: f32,
+    /// Probability of the no-speech token right after `<|startoftranscript|>`
+    pub no_speech_prob: f32,
+}
+
+/// Token sequence produced by one decoding strategy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hypothesis {
+    /// Generated tokens, without the end-of-text token
+    pub tokens: Vec<u32>,
+    /// Log-probability of each token in `tokens`
+    pub logprobs: Vec<f32>,
+    /// Sum of token log-probabilities, including a sampled end-of-text token
+    pub sum_logprob: f32,
+    /// Whether the end-of-text token was sampled (false when cut off by the length limit)
+    pub ended: bool,
+}
+
+impl Hypothesis {
+    /// Average log-probability per token, counting the end-of-text token if sampled
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn avg_logprob(&self) -> f32 {
+        self.sum_logprob / (self.tokens.len() + usize::from(self.ended)).max(1) as f32
+    }
+
+    /// Length-normalized score used to rank finished beams
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self) -> f32 {
+        self.sum_logprob / self.tokens.len().max(1) as f32
+    }
+}
+
+/// Decode with increasing temperature until the result passes the thresholds
+///
+/// `decode` runs one attempt at the given temperature. When every attempt fails,
+/// the most confident attempt that is not a repetition loop is kept; if all of
+/// them loop, `None` is returned so the garbage never reaches the user. Segments
+/// that are most likely silence are dropped the same way.
+pub fn decode_with_fallback(
+    options: &DecodingOptions,
+    mut decode: impl FnMut(f32) -> Result<DecodingResult>,
+) -> Result<Option<DecodingResult>> {
+    let mut best: Option<DecodingResult> = None;
+
+    for &temperature in &options.temperatures {
+        let result = decode(temperature)?;
+        if options.is_silence(&result) {
+            tracing::debug!(
+                no_speech_prob = result.no_speech_prob,
+                avg_logprob = result.avg_logprob,
+                text = %result.text,
+                "Discarding segment: no speech detected"
+            );
+            return Ok(None);
+        }
+        if !options.needs_fallback(&result) {
+            return Ok(Some(result));
+        }
+
+        tracing::debug!(
+            temperature,
+            avg_logprob = result.avg_logprob,
+            compression_ratio = result.compression_ratio,
+            "Decode failed thresholds"
+        );
+
+        let loops = result.compression_ratio > options.compression_ratio_threshold;
+        if !loops
+            && best
+                .as_ref()
+                .is_none_or(|b| result.avg_logprob > b.avg_logprob)
+        {
+            best = Some(result);
+        }
+    }
+
+    if best.is_none() {
+        tracing::warn!("Discarding segment: every decode attempt was a repetition loop");
+    }
+    Ok(best)
+}
+
+/// Decode one hypothesis at `temperature`
+///
+/// `step` returns next-token logits for the tokens generated so far. Beam search
+/// is used at temperature 0 when `beam_size > 1`; otherwise tokens are picked
+/// greedily (temperature 0) or sampled.
+pub fn decode_tokens(
+    options: &DecodingOptions,
+    temperature: f32,
+    step: impl FnMut(&[u32]) -> Result<Vec<f32>>,
+    eot_token: u32,
+    max_tokens: usize,
+) -> Result<Hypothesis> {
+    if temperature <= 0.0 && options.beam_size > 1 {
+        beam_search(
+            step,
+            eot_token,
+            max_tokens,
+            options.beam_size,
+            options.max_candidates(),
+        )
+    } else {
+        sample(step, eot_token, max_tokens, temperature)
+    }
+}
+
+/// Greedy (temperature 0) or temperature-sampled decoding
+fn sample(
+    mut step: impl FnMut(&[u32]) -> Result<Vec<f32>>,
+    eot_token: u32,
+    max_tokens: usize,
+    temperature: f32,
+) -> Result<Hypothesis> {
+    let mut processor = LogitsProcessor::new(SAMPLING_SEED, Some(f64::from(temperature)), None);
+    let mut hypothesis = Hypothesis::default();
+
+    for _ in 0..max_tokens {
+        let logits = step(&hypothesis.tokens)?;
+        let logprobs = log_softmax(&logits);
+
+        let token = Tensor::new(logits.as_slice(), &Device::Cpu)
+            .and_then(|t| processor.sample(&t))
+            .map_err(|e| {
+                ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                    "Sampling failed: {e}"
+                )))
+            })?;
+
+        let logprob = logprobs[token as usize];
+        hypothesis.sum_logprob += logprob;
+        if token == eot_token {
+            hypothesis.ended = true;
+            break;
+        }
+        hypothesis.tokens.push(token);
+        hypothesis.logprobs.push(logprob);
+    }
+
+    Ok(hypothesis)
+}
+
+/// Beam search as in Whisper's reference decoder
+///
+/// Each step expands every beam with its `beam_size + 1` most likely tokens and
+/// keeps the best `beam_size` unfinished sequences. Search stops once
+/// `max_candidates` sequences have ended, and the finished sequence with the best
+/// length-normalized log-probability wins.
+fn beam_search(
+    mut step: impl FnMut(&[u32]) -> Result<Vec<f32>>,
+    eot_token: u32,
+    max_tokens: usize,
+    beam_size: usize,
+    max_candidates: usize,
+) -> Result<Hypothesis> {
+    let mut beams = vec![Hypothesis::default()];
+    let mut finished: Vec<Hypothesis> = Vec::new();
+
+    for _ in 0..max_tokens {
+        let mut candidates = Vec::new();
+        for (index, beam) in beams.iter().enumerate() {
+            let logprobs = log_softmax(&step(&beam.tokens)?);
+            for (token, logprob) in top_k(&logprobs, beam_size + 1) {
+                candidates.push((index, token, logprob, beam.sum_logprob + logprob));
+            }
+        }
+        candidates.sort_by(|a, b| b.3.total_cmp(&a.3));
+
+        let mut next = Vec::with_capacity(beam_size);
+        for (index, token, logprob, sum_logprob) in candidates {
+            let beam = &beams[index];
+            if token == eot_token {
+                if finished.len() < max_candidates {
+                    finished.push(Hypothesis {
+                        sum_logprob,
+                        ended: true,
+                        ..beam.clone()
+                    });
+                }
+            } else {
+                let mut tokens = beam.tokens.clone();
+                tokens.push(token);
+                let mut logprobs = beam.logprobs.clone();
+                logprobs.push(logprob);
+                next.push(Hypothesis {
+                    tokens,
+                    logprobs,
+                    sum_logprob,
+                    ended: false,
+                });
+                if next.len() == beam_size {
+                    break;
+                }
+            }
+        }
+
+        beams = next;
+        if finished.len() >= max_candidates || beams.is_empty() {
+            break;
+        }
+    }
+
+    // Sequences cut off by the length limit still compete
+    if finished.len() < max_candidates {
+        finished.extend(beams);
+    }
+
+    Ok(finished
+        .into_iter()
+        .max_by(|a, b| a.score().total_cmp(&b.score()))
+        .unwrap_or_default())
+}
+
+/// The `k` most likely tokens, skipping suppressed (-inf) ones
+fn top_k(logprobs: &[f32], k: usize) -> Vec<(u32, f32)> {
+    let mut ranked: Vec<(u32, f32)> = (0u32..)
+        .zip(logprobs.iter().copied())
+        .filter(|(_, logprob)| logprob.is_finite())
+        .collect();
+
+    let k = k.min(ranked.len());
+    if k == 0 {
+        return Vec::new();
+    }
+
+    ranked.select_nth_unstable_by(k - 1, |a, b| b.1.total_cmp(&a.1));
+    ranked.truncate(k);
+    ranked
+}
+
+/// Whisper's timestamp rules, applied to next-token logits
+///
+/// Output starts with a timestamp within the first second, timestamps come in
+/// pairs around text and never decrease, and a timestamp is forced whenever all
+/// timestamps together are more likely than any single text token.
+pub fn apply_timestamp_rules(
+    logits: &mut [f32],
+    generated: &[u32],
+    eot_token: u32,
+    timestamp_begin: u32,
+) {
+    let len = logits.len();
+    let begin = (timestamp_begin as usize).min(len);
+    let is_timestamp = |token: u32| token >= timestamp_begin;
+    let suppress = |logits: &mut [f32]| logits.fill(f32::NEG_INFINITY);
+
+    let last_was_timestamp = generated.last().is_some_and(|&t| is_timestamp(t));
+    let penultimate_was_timestamp =
+        generated.len() < 2 || is_timestamp(generated[generated.len() - 2]);
+
+    if last_was_timestamp {
+        if penultimate_was_timestamp {
+            // A pair just closed (or the window just opened): text must follow
+            suppress(&mut logits[begin..]);
+        } else {
+            // Text just ended: close it with a timestamp or end the window
+            suppress(&mut logits[..(eot_token as usize).min(begin)]);
+        }
+    }
+
+    if let Some(&last) = generated.iter().rev().find(|&&t| is_timestamp(t)) {
+        // A closing timestamp may repeat the last one to open the next segment
+        let first_allowed = if last_was_timestamp && !penultimate_was_timestamp {
+            last
+        } else {
+            last + 1
+        };
+        suppress(&mut logits[begin..(first_allowed as usize).min(len)]);
+    }
+
+    if generated.is_empty() {
+        suppress(&mut logits[..begin]);
+        let first_disallowed = (begin + MAX_INITIAL_TIMESTAMP + 1).min(len);
+        suppress(&mut logits[first_disallowed..]);
+    }
+
+    let logprobs = log_softmax(logits);
+    let timestamp_logprob = log_sum_exp(&logprobs[begin..]);
+    let max_text_logprob = logprobs[..begin]
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    if timestamp_logprob > max_text_logprob {
+        suppress(&mut logits[..begin]);
+    }
+}
+
+/// Tokens of one timestamped segment, with times relative to the window start
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedTokens {
+    pub start: f64,
+    pub end: f64,
+    pub tokens: Vec<u32>,
+    /// Average log-probability of the segment's text tokens
+    pub avg_logprob: f32,
+}
+
+/// Split timestamped decoder output into segments of text tokens
+///
+/// `logprobs` holds the log-probability of each token. Text without a closing
+/// timestamp (cut off by the window) runs to `window_end`.
+pub fn split_timestamped(
+    tokens: &[u32],
+    logprobs: &[f32],
+    timestamp_begin: u32,
+    window_end: f64,
+) -> Vec<TimedTokens> {
+    let time = |token: u32| f64::from(token - timestamp_begin) * TIMESTAMP_STEP;
+
+    let mut segments = Vec::new();
+    let mut start = None;
+    let mut text = Vec::new();
+    let mut text_logprobs = Vec::new();
+
+    for (&token, &logprob) in tokens.iter().zip(logprobs) {
+        if token < timestamp_begin {
+            text.push(token);
+            text_logprobs.push(logprob);
+        } else if text.is_empty() {
+            start = Some(time(token));
+        } else {
+            segments.push(TimedTokens {
+                start: start.take().unwrap_or(0.0),
+                end: time(token),
+                tokens: std::mem::take(&mut text),
+                avg_logprob: mean(&std::mem::take(&mut text_logprobs)),
+            });
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(TimedTokens {
+            start: start.unwrap_or(0.0),
+            end: window_end,
+            tokens: text,
+            avg_logprob: mean(&text_logprobs),
+        });
+    }
+
+    segments
+}
+
+/// Arithmetic mean of `values` (0 when empty)
+#[allow(clippy::cast_precision_loss)]
+fn mean(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+/// Probability of `token` under the softmax of `logits`
+#[must_use]
+pub fn token_probability(logits: &[f32], token: u32) -> f32 {
+    log_softmax(logits)
+        .get(token as usize)
+        .map_or(0.0, |logprob| logprob.exp())
+}
+
+/// Log of the summed probabilities of `logprobs`
+fn log_sum_exp(logprobs: &[f32]) -> f32 {
+    let max = logprobs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    logprobs.iter().map(|l| (l - max).exp()).sum::<f32>().ln() + max
+}
+
+/// Convert logits to log-probabilities
+fn log_softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let log_sum = logits.iter().map(|l| (l - max).exp()).sum::<f32>().ln() + max;
+    logits.iter().map(|l| l - log_sum).collect()
+}
+
+/// Ratio of text bytes to zlib-compressed bytes
+///
+/// Repetition loops compress unusually well, so a high ratio marks a
+/// hallucinated decode.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compression_ratio(text: &str) -> f32 {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    let compressed_len = encoder
+        .write_all(text.as_bytes())
+        .and_then(|()| encoder.finish())
+        .map_or(1, |compressed| compressed.len().max(1));
+
+    text.len() as f32 / compressed_len as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOT: u32 = 3;
+
+    fn options(beam_size: usize) -> DecodingOptions {
+        DecodingOptions {
+            beam_size,
+            patience: 1.0,
+            temperatures: fallback_temperatures(0.2),
+            logprob_threshold: -1.0,
+            compression_ratio_threshold: 2.4,
+            no_speech_threshold: 0.6,
+            timestamps: false,
+        }
+    }
+
+    fn result(avg_logprob: f32, compression_ratio: f32, temperature: f32) -> DecodingResult {
+        DecodingResult {
+            tokens: vec![1],
+            token_logprobs: vec![avg_logprob],
+            text: format!("t={temperature}"),
+            avg_logprob,
+            compression_ratio,
+            temperature,
+            no_speech_prob: 0.0,
+        }
+    }
+
+    /// Logits from probabilities over a 4-token vocabulary (token 3 is EOT)
+    fn logits(probs: [f32; 4]) -> Vec<f32> {
+        probs.iter().map(|p| p.ln()).collect()
+    }
+
+    #[test]
+    fn test_fallback_temperatures() {
+        let temperatures = fallback_temperatures(0.2);
+        assert_eq!(temperatures.len(), 6);
+        assert!((temperatures[5] - 1.0).abs() < 1e-4);
+        assert_eq!(fallback_temperatures(0.0), vec![0.0]);
+        assert_eq!(fallback_temperatures(1.0), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        let normal = compression_ratio("The quick brown fox jumps over the lazy dog.");
+        let looping = compression_ratio(&"Thank you. ".repeat(30));
+        assert!(normal < 2.4, "normal text ratio {normal}");
+        assert!(looping > 2.4, "repetition loop ratio {looping}");
+        assert!(compression_ratio("").abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_greedy_decoding() {
+        // Always prefers token 1 until two tokens are generated, then ends
+        let step = |generated: &[u32]| {
+            Ok(if generated.len() < 2 {
+                logits([0.1, 0.6, 0.2, 0.1])
+            } else {
+                logits([0.1, 0.1, 0.1, 0.7])
+            })
+        };
+
+        let hypothesis = decode_tokens(&options(1), 0.0, step, EOT, 10).unwrap();
+        assert_eq!(hypothesis.tokens, vec![1, 1]);
+        let expected = (0.6f32 * 0.6 * 0.7).ln();
+        assert!((hypothesis.sum_logprob - expected).abs() < 1e-4);
+        assert!(hypothesis.ended);
+        assert!((hypothesis.avg_logprob() - expected / 3.0).abs() < 1e-4);
+        assert_eq!(hypothesis.logprobs.len(), 2);
+        assert!((hypothesis.logprobs[1] - 0.6f32.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_decoding_stops_at_max_tokens() {
+        // End-of-text is never possible, so only the length limit stops decoding
+        let step = |_: &[u32]| Ok(logits([0.15, 0.7, 0.15, 0.0]));
+        let hypothesis = decode_tokens(&options(1), 0.0, step, EOT, 5).unwrap();
+        assert_eq!(hypothesis.tokens, vec![1; 5]);
+        // No end-of-text token was sampled, so none is averaged in
+        assert!(!hypothesis.ended);
+        assert!((hypothesis.avg_logprob() - 0.7f32.ln()).abs() < 1e-4);
+
+        // Unfinished beams compete when the limit is hit
+        let hypothesis = decode_tokens(&options(3), 0.0, step, EOT, 5).unwrap();
+        assert_eq!(hypothesis.tokens, vec![1; 5]);
+        assert!(!hypothesis.ended);
+        assert!((hypothesis.avg_logprob() - 0.7f32.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_beam_search_beats_greedy() {
+        // Greedy takes token 1 first (0.5) but then faces an uncertain ending;
+        // token 2 (0.4) leads to a confident ending with higher total probability.
+        let step = |generated: &[u32]| {
+            Ok(match generated {
+                [] => logits([0.05, 0.5, 0.4, 0.05]),
+                [1] => logits([0.3, 0.3, 0.1, 0.3]),
+                [2] => logits([0.01, 0.01, 0.01, 0.97]),
+                _ => logits([0.1, 0.1, 0.1, 0.7]),
+            })
+        };
+
+        let greedy = decode_tokens(&options(1), 0.0, step, EOT, 10).unwrap();
+        assert_eq!(greedy.tokens[0], 1);
+
+        let beam = decode_tokens(&options(2), 0.0, step, EOT, 10).unwrap();
+        assert_eq!(beam.tokens, vec![2]);
+        assert_eq!(beam.logprobs.len(), 1);
+        assert!((beam.logprobs[0] - 0.4f32.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_sampling_is_reproducible() {
+        let step = |generated: &[u32]| {
+            Ok(if generated.len() < 8 {
+                logits([0.3, 0.3, 0.3, 0.1])
+            } else {
+                logits([0.0, 0.0, 0.0, 1.0])
+            })
+        };
+
+        let first = decode_tokens(&options(5), 1.0, step, EOT, 10).unwrap();
+        let second = decode_tokens(&options(5), 1.0, step, EOT, 10).unwrap();
+        assert_eq!(first, second);
+        assert!(first.tokens.iter().all(|&t| t < EOT));
+    }
+
+    #[test]
+    fn test_token_probability() {
+        let probs = logits([0.1, 0.2, 0.3, 0.4]);
+        assert!((token_probability(&probs, 2) - 0.3).abs() < 1e-5);
+        assert!(token_probability(&probs, 9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_split_timestamped() {
+        // <|0.00|> a b <|1.00|><|1.00|> c <|2.50|> d (cut off by the window)
+        let ts = |seconds: u32| 100 + seconds * 50;
+        let tokens = [ts(0), 1, 2, ts(1), ts(1), 3, 100 + 125, 4];
+        let logprobs = [-0.1, -0.2, -0.4, -0.1, -0.1, -2.0, -0.1, -0.5];
+        let segments = split_timestamped(&tokens, &logprobs, 100, 30.0);
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].tokens, vec![1, 2]);
+        // Confidence is per segment, from its text tokens only
+        assert!((segments[0].avg_logprob + 0.3).abs() < 1e-6);
+        assert!((segments[1].avg_logprob + 2.0).abs() < 1e-6);
+        assert!((segments[2].avg_logprob + 0.5).abs() < 1e-6);
+        assert!(segments[0].start.abs() < 1e-9);
+        assert!((segments[0].end - 1.0).abs() < 1e-9);
+        assert_eq!(segments[1].tokens, vec![3]);
+        assert!((segments[1].start - 1.0).abs() < 1e-9);
+        assert!((segments[1].end - 2.5).abs() < 1e-9);
+        assert_eq!(segments[2].tokens, vec![4]);
+        assert!((segments[2].end - 30.0).abs() < 1e-9);
+
+        assert!(split_timestamped(&[ts(0), ts(0)], &[-0.1, -0.1], 100, 30.0).is_empty());
+    }
+
+    #[test]
+    fn test_timestamp_rules() {
+        // Vocabulary: text 0..3, EOT 3, timestamps 4..
+        const BEGIN: u32 = 4;
+        let allowed = |generated: &[u32], logits: &[f32]| -> Vec<usize> {
+            let mut logits = logits.to_vec();
+            apply_timestamp_rules(&mut logits, generated, EOT, BEGIN);
+            (0..logits.len())
+                .filter(|&i| logits[i].is_finite())
+                .collect()
+        };
+        let flat = vec![0.0f32; 60];
+
+        // First token must be an early timestamp
+        let first = allowed(&[], &flat);
+        assert!(first
+            .iter()
+            .all(|t| (4..=4 + MAX_INITIAL_TIMESTAMP).contains(t)));
+
+        // After the opening timestamp, text follows
+        assert!(allowed(&[10], &flat).iter().all(|&t| t < 4));
+
+        // After text and a closing timestamp, only timestamps >= it or EOT
+        let after_close = allowed(&[10, 1, 20], &flat);
+        assert!(after_close.iter().all(|&t| t == 3 || t >= 20));
+        assert!(after_close.contains(&20));
+
+        // Timestamps never decrease within text
+        let in_text = allowed(&[10, 1], &flat);
+        assert!(!in_text.iter().any(|&t| (4..=10).contains(&t)));
+    }
+
+    #[test]
+    fn test_top_k_skips_suppressed() {
+        let top = top_k(&[-1.0, f32::NEG_INFINITY, -0.5, -3.0], 3);
+        let tokens: Vec<u32> = top.iter().map(|(t, _)| *t).collect();
+        assert_eq!(tokens.len(), 3);
+        assert!(!tokens.contains(&1));
+        assert!(top_k(&[f32::NEG_INFINITY], 2).is_empty());
+    }
+
+    #[test]
+    fn test_fallback_accepts_first_good_result() {
+        let mut tried = Vec::new();
+        let accepted = decode_with_fallback(&options(1), |temperature| {
+            tried.push(temperature);
+            Ok(if tried.len() < 3 {
+                result(-2.0, 1.5, temperature)
+            } else {
+                result(-0.3, 1.5, temperature)
+            })
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(tried.len(), 3);
+        assert!((accepted.temperature - tried[2]).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_fallback_keeps_most_confident_non_loop() {
+        let accepted = decode_with_fallback(&options(1), |temperature| {
+            Ok(if temperature < 0.3 {
+                result(-1.2, 1.5, temperature)
+            } else {
+                result(-1.5, 1.5, temperature)
+            })
+        })
+        .unwrap()
+        .unwrap();
+
+        assert!((accepted.avg_logprob + 1.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_silence_is_discarded_without_fallback() {
+        let mut attempts = 0;
+        let accepted = decode_with_fallback(&options(1), |temperature| {
+            attempts += 1;
+            Ok(DecodingResult {
+                no_speech_prob: 0.9,
+                ..result(-1.5, 1.0, temperature)
+            })
+        })
+        .unwrap();
+
+        assert!(accepted.is_none());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_confident_hallucination_is_discarded() {
+        let thank_you = |temperature| {
+            Ok(DecodingResult {
+                text: " Thank you.".to_string(),
+                no_speech_prob: 0.9,
+                ..result(-0.2, 1.0, temperature)
+            })
+        };
+
+        let accepted = decode_with_fallback(&options(1), thank_you).unwrap();
+        assert!(accepted.is_none());
+
+        // A threshold of 1.0 keeps everything
+        let options = DecodingOptions {
+            no_speech_threshold: 1.0,
+            ..options(1)
+        };
+        let accepted = decode_with_fallback(&options, thank_you).unwrap();
+        assert!(accepted.is_some());
+    }
+
+    #[test]
+    fn test_step_error_stops_decoding() {
+        // A failing step (a cancelled job) ends beam search and the fallback at once
+        let mut attempts = 0;
+        let mut steps = 0;
+        let outcome = decode_with_fallback(&options(3), |temperature| {
+            attempts += 1;
+            let step = |_: &[u32]| {
+                steps += 1;
+                Err(ScribeError::Transcription(TranscriptionError::Cancelled))
+            };
+            decode_tokens(&options(3), temperature, step, EOT, 10)
+                .map(|hypothesis| result(hypothesis.avg_logprob(), 1.0, temperature))
+        });
+
+        assert!(matches!(
+            outcome,
+            Err(ScribeError::Transcription(TranscriptionError::Cancelled))
+        ));
+        assert_eq!((attempts, steps), (1, 1));
+    }
+
+    #[test]
+    fn test_fallback_discards_repetition_loops() {
+        let accepted = decode_with_fallback(&options(1), |temperature| {
+            Ok(result(-0.2, 5.0, temperature))
+        })
+        .unwrap();
+        assert!(accepted.is_none());
+    }
+}
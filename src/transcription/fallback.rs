@@ -0,0 +1,120 @@
+use crate::config::schema::TranscriptionConfig;
+use crate::error::{Result, ScribeError};
+use crate::transcription::{LocalBackend, Task, Transcription, TranscriptionBackend};
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+
+/// API backend that re-runs failed transcriptions through a local model
+///
+/// Only failures of the service itself (network errors, server errors and
+/// exhausted quotas) fall back; configuration and request errors are returned
+/// as they are. The local model is loaded on first failure, so the daemon
+/// starts quickly and keeps its memory free while the API works.
+pub struct FallbackBackend {
+    primary: Box<dyn TranscriptionBackend>,
+    config: TranscriptionConfig,
+    local: OnceCell<LocalBackend>,
+}
+
+impl std::fmt::Debug for FallbackBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackBackend")
+            .field("primary", &self.primary.backend_name())
+            .field("model", &self.config.model)
+            .field("local", &self.local.get())
+            .finish()
+    }
+}
+
+impl FallbackBackend {
+    /// Wrap `primary`, falling back to the local model described by `config`
+    #[must_use]
+    pub fn new(primary: Box<dyn TranscriptionBackend>, config: &TranscriptionConfig) -> Self {
+        Self {
+            primary,
+            config: config.clone(),
+            local: OnceCell::new(),
+        }
+    }
+
+    /// Local backend, loading the model on first use
+    async fn local(&self) -> Result<&LocalBackend> {
+        self.local
+            .get_or_try_init(|| async {
+                tracing::info!(model = %self.config.model, "Loading local fallback backend");
+                LocalBackend::new(&self.config).await
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for FallbackBackend {
+    async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String> {
+        Ok(self.transcribe_segments(audio, task).await?.text)
+    }
+
+    async fn transcribe_segments(&self, audio: &[i16], task: Task) -> Result<Transcription> {
+        let error = match self.primary.transcribe_segments(audio, task).await {
+            Ok(transcription) => return Ok(transcription),
+            Err(e) if should_fall_back(&e) => e,
+            Err(e) => return Err(e),
+        };
+        tracing::warn!(
+            backend = %self.primary.backend_name(),
+            error = %error,
+            "Transcription failed, falling back to local backend"
+        );
+
+        // Report the original failure if the fallback cannot even load
+        let local = match self.local().await {
+            Ok(local) => local,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load local fallback backend");
+                return Err(error);
+            }
+        };
+        local.transcribe_segments(audio, task).await
+    }
+
+    fn backend_name(&self) -> &str {
+        self.primary.backend_name()
+    }
+
+    fn detected_language(&self) -> Option<String> {
+        self.local
+            .get()
+            .and_then(LocalBackend::detected_language)
+            .or_else(|| self.primary.detected_language())
+    }
+}
+
+/// Whether `error` means the API failed, rather than the request or its configuration
+const fn should_fall_back(error: &ScribeError) -> bool {
+    matches!(error, ScribeError::Transcription(e) if e.is_transient())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TranscriptionError;
+
+    #[test]
+    fn test_should_fall_back() {
+        for error in [
+            TranscriptionError::NetworkError("timed out".to_string()),
+            TranscriptionError::ServerError("503".to_string()),
+            TranscriptionError::QuotaExceeded,
+        ] {
+            assert!(should_fall_back(&ScribeError::Transcription(error)));
+        }
+
+        for error in [
+            TranscriptionError::InvalidApiKey,
+            TranscriptionError::ApiError("Bad request: bad audio".to_string()),
+        ] {
+            assert!(!should_fall_back(&ScribeError::Transcription(error)));
+        }
+        assert!(!should_fall_back(&ScribeError::Config("bad".to_string())));
+    }
+}
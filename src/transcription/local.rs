@@ -1,39 +1,147 @@
 use crate::config::schema::TranscriptionConfig;
 use crate::error::{Result, ScribeError, TranscriptionError};
-use crate::transcription::TranscriptionBackend;
+use crate::models::{ModelFiles, ModelManager, ModelSource};
+use crate::transcription::decoding::{self, DecodingOptions, DecodingResult, TimedTokens};
+use crate::transcription::{Segment, Task, Transcription, TranscriptionBackend, SAMPLE_RATE};
 use anyhow::Error as E;
 use async_trait::async_trait;
 use byteorder::{ByteOrder, LittleEndian};
 use candle_core::{Device, IndexOp, Tensor};
 use candle_nn::VarBuilder;
 use candle_transformers::models::whisper::{self as m, audio, Config};
-use hf_hub::{api::tokio::Api, Repo, RepoType};
-use std::sync::{Arc, Mutex};
+use candle_transformers::quantized_var_builder;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 use tokenizers::Tokenizer;
 
+/// Token that introduces previous-context text in the decoder prompt
+const SOT_PREV_TOKEN: &str = "<|startofprev|>";
+
+/// Vocabulary size from which a checkpoint is multilingual (English-only models use 51864)
+const MULTILINGUAL_VOCAB_SIZE: usize = 51865;
+
+/// Above this temperature a segment's output is too unreliable to condition the next one on
+const PROMPT_RESET_TEMPERATURE: f32 = 0.5;
+
+/// Window (in samples) at the end of each segment searched for a quiet cut point
+const CUT_SEARCH_SAMPLES: usize = 5 * m::SAMPLE_RATE;
+
+/// Frame size (in samples) used when measuring energy for cut points (30ms)
+const CUT_FRAME_SAMPLES: usize = 480;
+
 /// Wrapper around Whisper model variants
 enum WhisperModel {
     Normal(m::model::Whisper),
+    Quantized(m::quantized_model::Whisper),
 }
 
 /// Parameters for decoding
 struct DecodeParams<'a> {
     model: &'a mut WhisperModel,
     tokenizer: &'a Tokenizer,
-    mel: &'a Tensor,
+    audio_features: &'a Tensor,
     device: &'a Device,
     config: &'a Config,
+    options: &'a DecodingOptions,
+    prompt_tokens: &'a [u32],
     language_token: Option<u32>,
-    sot_token: u32,
-    transcribe_token: u32,
-    eot_token: u32,
-    no_timestamps_token: u32,
+    task: Task,
+    special: SpecialTokens,
+    cancelled: &'a AtomicBool,
+}
+
+/// Sets its flag when dropped, telling blocking inference to stop
+///
+/// Aborting the task that awaits a transcription drops the guard, so decoding
+/// stops at the next step instead of running through the rest of the recording
+/// while holding the model.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Special token ids of the loaded model's vocabulary
+#[derive(Debug, Clone, Copy)]
+struct SpecialTokens {
+    sot: u32,
+    sot_prev: u32,
+    /// Task tokens, absent for English-only vocabularies
+    transcribe: Option<u32>,
+    /// Also absent for models that only produce English
+    translate: Option<u32>,
+    eot: u32,
+    no_timestamps: u32,
+    /// `<|nospeech|>` (`<|nocaptions|>` in older vocabularies), if the model has one
+    no_speech: Option<u32>,
+}
+
+impl SpecialTokens {
+    /// Look up special tokens in the tokenizer vocabulary
+    ///
+    /// `english_only` models keep the task tokens of a multilingual vocabulary
+    /// but cannot translate.
+    fn from_tokenizer(
+        tokenizer: &Tokenizer,
+        multilingual: bool,
+        english_only: bool,
+    ) -> Result<Self> {
+        let (transcribe, translate) = if multilingual {
+            (
+                Some(LocalBackend::token_id(tokenizer, m::TRANSCRIBE_TOKEN)?),
+                (!english_only)
+                    .then(|| LocalBackend::token_id(tokenizer, m::TRANSLATE_TOKEN))
+                    .transpose()?,
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            sot: LocalBackend::token_id(tokenizer, m::SOT_TOKEN)?,
+            sot_prev: LocalBackend::token_id(tokenizer, SOT_PREV_TOKEN)?,
+            transcribe,
+            translate,
+            eot: LocalBackend::token_id(tokenizer, m::EOT_TOKEN)?,
+            no_timestamps: LocalBackend::token_id(tokenizer, m::NO_TIMESTAMPS_TOKEN)?,
+            no_speech: m::NO_SPEECH_TOKENS
+                .iter()
+                .find_map(|token| tokenizer.token_to_id(token)),
+        })
+    }
+
+    /// Token selecting `task`
+    const fn task(&self, task: Task) -> Option<u32> {
+        match task {
+            Task::Transcribe => self.transcribe,
+            Task::Translate => self.translate,
+        }
+    }
+
+    /// First timestamp token (`<|0.00|>`), which follows `<|notimestamps|>`
+    const fn timestamp_begin(&self) -> u32 {
+        self.no_timestamps + 1
+    }
 }
 
 impl WhisperModel {
+    /// Encode a mel spectrogram into audio features
+    fn encode(&mut self, mel: &Tensor) -> Result<Tensor> {
+        self.encoder_forward(mel, true).map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                "Encoder forward failed: {e}"
+            )))
+        })
+    }
+
     fn encoder_forward(&mut self, x: &Tensor, flush: bool) -> candle_core::Result<Tensor> {
         match self {
             Self::Normal(m) => m.encoder.forward(x, flush),
+            Self::Quantized(m) => m.encoder.forward(x, flush),
         }
     }
 
@@ -45,12 +153,14 @@ impl WhisperModel {
     ) -> candle_core::Result<Tensor> {
         match self {
             Self::Normal(m) => m.decoder.forward(x, xa, flush),
+            Self::Quantized(m) => m.decoder.forward(x, xa, flush),
         }
     }
 
     fn decoder_final_linear(&self, x: &Tensor) -> candle_core::Result<Tensor> {
         match self {
             Self::Normal(m) => m.decoder.final_linear(x),
+            Self::Quantized(m) => m.decoder.final_linear(x),
         }
     }
 }
@@ -62,11 +172,15 @@ pub struct LocalBackend {
     device: Device,
     mel_filters: Arc<Vec<f32>>,
     config: Config,
+    decoding: DecodingOptions,
+    /// Encoded `initial_prompt`, prepended to every segment's context
+    initial_prompt_tokens: Vec<u32>,
     language_token: Option<u32>,
-    sot_token: u32,
-    transcribe_token: u32,
-    eot_token: u32,
-    no_timestamps_token: u32,
+    /// Language codes and tokens to choose from; empty unless auto-detecting
+    language_tokens: Vec<(String, u32)>,
+    /// Language detected in the most recent transcription
+    detected_language: Mutex<Option<String>>,
+    special: SpecialTokens,
 }
 
 impl std::fmt::Debug for LocalBackend {
@@ -84,29 +198,77 @@ impl LocalBackend {
         // Determine device
         let device = Self::get_device(&config.device)?;
 
-        // Load model and tokenizer from HuggingFace Hub
+        // Resolve installed model files; never download at startup
+        let files = ModelManager::new()?.resolve(&config.model, &config.quantization)?;
+        let quantized = config.quantization != "none";
+
+        // Load model and tokenizer off the async runtime
+        tracing::info!(
+            model = %config.model,
+            quantization = %config.quantization,
+            "Loading Whisper model"
+        );
+        let load_device = device.clone();
         let (model_config, tokenizer, model, mel_filters) =
-            Self::load_model(&config.model, &device).await?;
+            tokio::task::spawn_blocking(move || Self::load_model(&files, quantized, &load_device))
+                .await
+                .map_err(|e| {
+                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                        "Model loading task failed: {e}"
+                    )))
+                })??;
+
+        // English-only checkpoints (.en) take neither a language nor a task token
+        let multilingual = model_config.vocab_size >= MULTILINGUAL_VOCAB_SIZE;
+
+        // Some registry models (distil-large-v3) only produce English despite
+        // a multilingual vocabulary; they are always told the language is English
+        let english_only = !multilingual
+            || matches!(
+                ModelSource::parse(&config.model),
+                Ok(ModelSource::Registry(info)) if !info.multilingual
+            );
+        let language = match config.language.as_str() {
+            "" if english_only && multilingual => "en",
+            language @ ("" | "en") => language,
+            language if english_only => {
+                return Err(ScribeError::Transcription(TranscriptionError::ModelError(
+                    format!(
+                        "Model '{}' is English-only and cannot transcribe '{language}'. Set language = \"en\" or use a multilingual model",
+                        config.model
+                    ),
+                )))
+            }
+            language => language,
+        };
 
         // Get language token if specified
-        let language_token = if config.language.is_empty() {
-            None
-        } else {
-            Some(
-                Self::token_id(&tokenizer, &format!("<|{}|>", config.language)).map_err(|_| {
+        let language_token = match (multilingual, language) {
+            (_, "") | (false, _) => None,
+            (true, language) => Some(
+                Self::token_id(&tokenizer, &format!("<|{language}|>")).map_err(|_| {
                     ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Language '{}' not supported by model",
-                        config.language
+                        "Language '{language}' not supported by model"
                     )))
                 })?,
-            )
+            ),
+        };
+
+        // Empty language on a multilingual model means detect it per recording
+        let language_tokens = if multilingual && language.is_empty() {
+            Self::language_tokens(tokenizer.get_vocab(true))
+        } else {
+            Vec::new()
+        };
+
+        let initial_prompt_tokens = match config.initial_prompt.as_deref().map(str::trim) {
+            Some(prompt) if !prompt.is_empty() => {
+                Self::encode_prompt(&tokenizer, prompt, &model_config)?
+            }
+            _ => Vec::new(),
         };
 
-        // Get special tokens
-        let sot_token = Self::token_id(&tokenizer, m::SOT_TOKEN)?;
-        let transcribe_token = Self::token_id(&tokenizer, m::TRANSCRIBE_TOKEN)?;
-        let eot_token = Self::token_id(&tokenizer, m::EOT_TOKEN)?;
-        let no_timestamps_token = Self::token_id(&tokenizer, m::NO_TIMESTAMPS_TOKEN)?;
+        let special = SpecialTokens::from_tokenizer(&tokenizer, multilingual, english_only)?;
 
         Ok(Self {
             model: Arc::new(Mutex::new(model)),
@@ -114,11 +276,12 @@ impl LocalBackend {
             device,
             mel_filters: Arc::new(mel_filters),
             config: model_config,
+            decoding: DecodingOptions::from_config(config),
+            initial_prompt_tokens,
             language_token,
-            sot_token,
-            transcribe_token,
-            eot_token,
-            no_timestamps_token,
+            language_tokens,
+            detected_language: Mutex::new(None),
+            special,
         })
     }
 
@@ -138,60 +301,17 @@ impl LocalBackend {
         }
     }
 
-    /// Load model from `HuggingFace` Hub
-    async fn load_model(
-        model_size: &str,
+    /// Load model from installed model files
+    ///
+    /// With `quantized`, the weights file is GGUF rather than safetensors.
+    fn load_model(
+        files: &ModelFiles,
+        quantized: bool,
         device: &Device,
     ) -> Result<(Config, Tokenizer, WhisperModel, Vec<f32>)> {
-        // Map model size to HuggingFace repo
-        let (model_id, revision) = match model_size {
-            "tiny" => ("openai/whisper-tiny", "main"),
-            "base" => ("openai/whisper-base", "refs/pr/22"),
-            "small" => ("openai/whisper-small", "main"),
-            "medium" => ("openai/whisper-medium", "main"),
-            "large" => ("openai/whisper-large-v3", "main"),
-            _ => {
-                return Err(ScribeError::Transcription(TranscriptionError::ModelError(
-                    format!("Invalid model size: {model_size}"),
-                )))
-            }
-        };
-
-        // Download model files from HuggingFace Hub
-        tracing::info!("Loading Whisper model: {}", model_id);
-        let api = Api::new().map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Failed to initialize HuggingFace API: {e}"
-            )))
-        })?;
-
-        let repo = api.repo(Repo::with_revision(
-            model_id.to_string(),
-            RepoType::Model,
-            revision.to_string(),
-        ));
-
-        let config_path = repo.get("config.json").await.map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Failed to download config.json: {e}"
-            )))
-        })?;
-
-        let tokenizer_path = repo.get("tokenizer.json").await.map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Failed to download tokenizer.json: {e}"
-            )))
-        })?;
-
-        let weights_path = repo.get("model.safetensors").await.map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Failed to download model.safetensors: {e}"
-            )))
-        })?;
-
         // Load config
         let config: Config =
-            serde_json::from_str(&std::fs::read_to_string(&config_path).map_err(|e| {
+            serde_json::from_str(&std::fs::read_to_string(&files.config).map_err(|e| {
                 ScribeError::Transcription(TranscriptionError::ModelError(format!(
                     "Failed to read config: {e}"
                 )))
@@ -203,7 +323,7 @@ impl LocalBackend {
             })?;
 
         // Load tokenizer
-        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+        let tokenizer = Tokenizer::from_file(&files.tokenizer)
             .map_err(E::msg)
             .map_err(|e| {
                 ScribeError::Transcription(TranscriptionError::ModelError(format!(
@@ -226,22 +346,41 @@ impl LocalBackend {
         LittleEndian::read_f32_into(mel_bytes, &mut mel_filters);
 
         // Load model weights
-        let vb = unsafe {
-            VarBuilder::from_mmaped_safetensors(&[weights_path], m::DTYPE, device).map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                    "Failed to load model weights: {e}"
-                )))
-            })?
-        };
+        let model =
+            if quantized {
+                let vb = quantized_var_builder::VarBuilder::from_gguf(&files.weights, device)
+                    .map_err(|e| {
+                        ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                            "Failed to load quantized model weights: {e}"
+                        )))
+                    })?;
 
-        let model = m::model::Whisper::load(&vb, config.clone()).map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Failed to initialize model: {e}"
-            )))
-        })?;
+                WhisperModel::Quantized(
+                    m::quantized_model::Whisper::load(&vb, config.clone()).map_err(|e| {
+                        ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                            "Failed to initialize quantized model: {e}"
+                        )))
+                    })?,
+                )
+            } else {
+                let vb = unsafe {
+                    VarBuilder::from_mmaped_safetensors(&[&files.weights], m::DTYPE, device)
+                        .map_err(|e| {
+                            ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                                "Failed to load model weights: {e}"
+                            )))
+                        })?
+                };
+
+                WhisperModel::Normal(m::model::Whisper::load(&vb, config.clone()).map_err(|e| {
+                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                        "Failed to initialize model: {e}"
+                    )))
+                })?)
+            };
 
         tracing::info!("Model loaded successfully");
-        Ok((config, tokenizer, WhisperModel::Normal(model), mel_filters))
+        Ok((config, tokenizer, model, mel_filters))
     }
 
     /// Get token ID from tokenizer
@@ -253,114 +392,381 @@ impl LocalBackend {
         })
     }
 
-    /// Run inference on mel spectrogram (non-async, for use in blocking context)
-    fn decode_blocking(params: DecodeParams) -> Result<String> {
+    /// Encode the initial prompt, keeping only what fits the prompt budget
+    fn encode_prompt(tokenizer: &Tokenizer, prompt: &str, config: &Config) -> Result<Vec<u32>> {
+        let encoding = tokenizer.encode(format!(" {prompt}"), false).map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                "Failed to encode initial prompt: {e}"
+            )))
+        })?;
+
+        let tokens = encoding.get_ids();
+        let budget = Self::prompt_budget(config);
+        if tokens.len() > budget {
+            tracing::warn!(
+                token_count = tokens.len(),
+                budget,
+                "Initial prompt too long, keeping only its end"
+            );
+        }
+        Ok(Self::truncate_prompt(tokens, budget).to_vec())
+    }
+
+    /// Language tokens (`<|en|>`, `<|no|>`, ...) in a tokenizer vocabulary, by token id
+    fn language_tokens(vocab: HashMap<String, u32>) -> Vec<(String, u32)> {
+        let mut languages: Vec<(String, u32)> = vocab
+            .into_iter()
+            .filter_map(|(token, id)| {
+                let code = token.strip_prefix("<|")?.strip_suffix("|>")?;
+                let is_language =
+                    matches!(code.len(), 2 | 3) && code.bytes().all(|b| b.is_ascii_lowercase());
+                is_language.then(|| (code.to_string(), id))
+            })
+            .collect();
+        languages.sort_by_key(|&(_, id)| id);
+        languages
+    }
+
+    /// Whisper's language-ID step: pick the most likely language token after
+    /// `<|startoftranscript|>`
+    ///
+    /// Returns the language code, its token and its probability among languages.
+    fn detect_language(
+        model: &mut WhisperModel,
+        audio_features: &Tensor,
+        sot_token: u32,
+        language_tokens: &[(String, u32)],
+        device: &Device,
+    ) -> Result<(String, u32, f32)> {
+        let logits =
+            Self::next_token_logits(model, audio_features, &[sot_token], &[], device, true)?;
+        let language_logits: Vec<f32> = language_tokens
+            .iter()
+            .map(|&(_, token)| {
+                logits
+                    .get(token as usize)
+                    .copied()
+                    .unwrap_or(f32::NEG_INFINITY)
+            })
+            .collect();
+
+        let (index, max) = language_logits
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .ok_or_else(|| {
+                ScribeError::Transcription(TranscriptionError::ModelError(
+                    "Model has no language tokens".to_string(),
+                ))
+            })?;
+        let probability = 1.0 / language_logits.iter().map(|l| (l - max).exp()).sum::<f32>();
+
+        let (code, token) = &language_tokens[index];
+        Ok((code.clone(), *token, probability))
+    }
+
+    /// Maximum number of context tokens after `<|startofprev|>`
+    ///
+    /// Whisper reserves half of the decoder context for the prompt.
+    const fn prompt_budget(config: &Config) -> usize {
+        config.max_target_positions / 2 - 1
+    }
+
+    /// Keep the last `budget` prompt tokens, which are closest to the new audio
+    fn truncate_prompt(tokens: &[u32], budget: usize) -> &[u32] {
+        &tokens[tokens.len().saturating_sub(budget)..]
+    }
+
+    /// Split audio into segments that fit Whisper's 30-second window
+    ///
+    /// Audio longer than one window is cut at the quietest 30ms frame within the
+    /// last few seconds of each window, so words are not split across segments.
+    fn segment_boundaries(samples: &[f32]) -> Vec<Range<usize>> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+
+        while samples.len() - start > m::N_SAMPLES {
+            let window_end = start + m::N_SAMPLES;
+            let search_start = window_end - CUT_SEARCH_SAMPLES;
+            let cut = search_start + Self::quietest_offset(&samples[search_start..window_end]);
+            segments.push(start..cut);
+            start = cut;
+        }
+
+        segments.push(start..samples.len());
+        segments
+    }
+
+    /// Find the offset of the lowest-energy frame in `samples`
+    fn quietest_offset(samples: &[f32]) -> usize {
+        samples
+            .chunks_exact(CUT_FRAME_SAMPLES)
+            .map(|frame| frame.iter().map(|s| s * s).sum::<f32>())
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(samples.len(), |(i, _)| i * CUT_FRAME_SAMPLES)
+    }
+
+    /// Compute a 30-second mel spectrogram tensor for one audio segment
+    fn segment_mel(
+        config: &Config,
+        samples: &[f32],
+        mel_filters: &[f32],
+        device: &Device,
+    ) -> Result<Tensor> {
+        // pcm_to_mel pads its input, so the result always covers at least one full window
+        let mel = audio::pcm_to_mel(config, samples, mel_filters);
+        let mel_len = mel.len();
+        let num_mel_bins = config.num_mel_bins;
+
+        Tensor::from_vec(mel, (1, num_mel_bins, mel_len / num_mel_bins), device)
+            .and_then(|t| t.narrow(2, 0, m::N_FRAMES))
+            .map_err(|e| {
+                ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                    "Failed to create mel tensor: {e}"
+                )))
+            })
+    }
+
+    /// Run inference on encoded audio (non-async, for use in blocking context)
+    ///
+    /// Returns `None` when the segment is most likely silence or every decode
+    /// attempt was a repetition loop.
+    fn decode_blocking(params: DecodeParams) -> Result<Option<DecodingResult>> {
         let DecodeParams {
             model,
             tokenizer,
-            mel,
+            audio_features,
             device,
             config,
+            options,
+            prompt_tokens,
             language_token,
-            sot_token,
-            transcribe_token,
-            eot_token,
-            no_timestamps_token,
+            task,
+            special,
+            cancelled,
         } = params;
-        // Encode audio to features
-        let audio_features = model.encoder_forward(mel, true).map_err(|e| {
-            ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                "Encoder forward failed: {e}"
-            )))
-        })?;
 
-        // Initialize token sequence
-        let mut tokens = vec![sot_token];
+        // Initialize token sequence, prefixed with previous context if available
+        let mut prefix = Vec::new();
+        if !prompt_tokens.is_empty() {
+            prefix.push(special.sot_prev);
+            prefix.extend_from_slice(Self::truncate_prompt(
+                prompt_tokens,
+                Self::prompt_budget(config),
+            ));
+        }
+        let sot_index = prefix.len();
+        prefix.push(special.sot);
         if let Some(lang_token) = language_token {
-            tokens.push(lang_token);
+            prefix.push(lang_token);
+        }
+        if let Some(task_token) = special.task(task) {
+            prefix.push(task_token);
+        }
+        if !options.timestamps {
+            prefix.push(special.no_timestamps);
         }
-        tokens.push(transcribe_token);
-        tokens.push(no_timestamps_token);
 
-        // Autoregressive decoding
-        let sample_len = config.max_target_positions / 2;
-        for i in 0..sample_len {
-            let tokens_t = Tensor::new(tokens.as_slice(), device).map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                    "Failed to create token tensor: {e}"
-                )))
-            })?;
+        let max_tokens = (config.max_target_positions / 2)
+            .min(config.max_target_positions.saturating_sub(prefix.len()));
 
-            let tokens_t = tokens_t.unsqueeze(0).map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                    "Failed to unsqueeze tokens: {e}"
-                )))
-            })?;
+        // Only text tokens, end-of-text and (when enabled) timestamps may be generated
+        let special_end = if options.timestamps {
+            special.timestamp_begin() as usize
+        } else {
+            config.vocab_size
+        };
+        let suppressed: Vec<usize> = config
+            .suppress_tokens
+            .iter()
+            .map(|&t| t as usize)
+            .chain(special.eot as usize + 1..special_end)
+            .collect();
 
-            let ys = model
-                .decoder_forward(&tokens_t, &audio_features, i == 0)
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Decoder forward failed: {e}"
-                    )))
-                })?;
+        // Whisper predicts the no-speech token right after <|startoftranscript|>
+        let no_speech_prob = match special.no_speech {
+            Some(token) => {
+                let logits = Self::next_token_logits(
+                    model,
+                    audio_features,
+                    &prefix[..=sot_index],
+                    &[],
+                    device,
+                    true,
+                )?;
+                decoding::token_probability(&logits, token)
+            }
+            None => 0.0,
+        };
 
-            let logits = model
-                .decoder_final_linear(&ys.i((..1,)).map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Failed to slice ys: {e}"
-                    )))
-                })?)
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Final linear failed: {e}"
-                    )))
-                })?
-                .i(0)
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Failed to index logits: {e}"
-                    )))
-                })?
-                .i(tokens.len() - 1)
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Failed to index logits: {e}"
-                    )))
-                })?;
+        decoding::decode_with_fallback(options, |temperature| {
+            Self::check_cancelled(cancelled)?;
 
-            let next_token = logits
-                .argmax(0)
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Argmax failed: {e}"
-                    )))
-                })?
-                .to_scalar::<u32>()
-                .map_err(|e| {
-                    ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                        "Failed to convert token to scalar: {e}"
-                    )))
-                })?;
+            // Checked every step, so sampling and beam search stop mid-window
+            let step = |generated: &[u32]| {
+                Self::check_cancelled(cancelled)?;
+                let mut logits = Self::next_token_logits(
+                    model,
+                    audio_features,
+                    &[prefix.as_slice(), generated].concat(),
+                    &suppressed,
+                    device,
+                    generated.is_empty(),
+                )?;
+                if options.timestamps {
+                    decoding::apply_timestamp_rules(
+                        &mut logits,
+                        generated,
+                        special.eot,
+                        special.timestamp_begin(),
+                    );
+                }
+                Ok(logits)
+            };
+            let hypothesis =
+                decoding::decode_tokens(options, temperature, step, special.eot, max_tokens)?;
 
-            tokens.push(next_token);
+            let text_tokens: Vec<u32> = hypothesis
+                .tokens
+                .iter()
+                .copied()
+                .filter(|&t| t < special.eot)
+                .collect();
+            let text = Self::decode_text(tokenizer, &text_tokens)?;
 
-            if next_token == eot_token {
-                break;
+            Ok(DecodingResult {
+                avg_logprob: hypothesis.avg_logprob(),
+                compression_ratio: decoding::compression_ratio(&text),
+                tokens: hypothesis.tokens,
+                token_logprobs: hypothesis.logprobs,
+                text,
+                temperature,
+                no_speech_prob,
+            })
+        })
+    }
+
+    /// Decode text tokens into a string
+    fn decode_text(tokenizer: &Tokenizer, tokens: &[u32]) -> Result<String> {
+        tokenizer.decode(tokens, true).map_err(E::msg).map_err(|e| {
+            ScribeError::Transcription(TranscriptionError::ModelError(format!(
+                "Failed to decode tokens: {e}"
+            )))
+        })
+    }
+
+    /// Timed segments of one decoded 30-second window
+    ///
+    /// `window` is the window's time span in the recording. Without timestamp
+    /// decoding the whole window becomes a single segment.
+    fn timed_segments(
+        tokenizer: &Tokenizer,
+        result: &DecodingResult,
+        timestamp_begin: Option<u32>,
+        window: Range<f64>,
+    ) -> Result<Vec<Segment>> {
+        let duration = window.end - window.start;
+        let pieces = timestamp_begin.map_or_else(
+            || {
+                vec![TimedTokens {
+                    start: 0.0,
+                    end: duration,
+                    tokens: result.tokens.clone(),
+                    avg_logprob: result.avg_logprob,
+                }]
+            },
+            |begin| {
+                decoding::split_timestamped(&result.tokens, &result.token_logprobs, begin, duration)
+            },
+        );
+
+        let mut segments = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            let text = Self::decode_text(tokenizer, &piece.tokens)?;
+            let text = text.trim();
+            if !text.is_empty() {
+                segments.push(Segment {
+                    start: window.start + piece.start,
+                    end: window.start + piece.end.min(duration),
+                    text: text.to_string(),
+                    avg_logprob: piece.avg_logprob,
+                });
             }
         }
+        Ok(segments)
+    }
 
-        // Decode tokens to text
-        let text = tokenizer
-            .decode(&tokens, true)
-            .map_err(E::msg)
-            .map_err(|e| {
-                ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                    "Failed to decode tokens: {e}"
-                )))
-            })?;
+    /// Convert a sample position to seconds
+    #[allow(clippy::cast_precision_loss)]
+    fn seconds(samples: usize) -> f64 {
+        samples as f64 / f64::from(SAMPLE_RATE)
+    }
+
+    /// Run the decoder on `tokens` and return logits for the next token
+    ///
+    /// `flush` rebuilds the cross-attention cache, which is needed on the first
+    /// step of every decode attempt.
+    fn next_token_logits(
+        model: &mut WhisperModel,
+        audio_features: &Tensor,
+        tokens: &[u32],
+        suppressed: &[usize],
+        device: &Device,
+        flush: bool,
+    ) -> Result<Vec<f32>> {
+        let model_error =
+            |msg: String| ScribeError::Transcription(TranscriptionError::ModelError(msg));
+
+        let tokens_t = Tensor::new(tokens, device)
+            .and_then(|t| t.unsqueeze(0))
+            .map_err(|e| model_error(format!("Failed to create token tensor: {e}")))?;
 
-        Ok(text)
+        let ys = model
+            .decoder_forward(&tokens_t, audio_features, flush)
+            .map_err(|e| model_error(format!("Decoder forward failed: {e}")))?;
+
+        let mut logits: Vec<f32> = ys
+            .i((..1, tokens.len() - 1..))
+            .and_then(|last| model.decoder_final_linear(&last))
+            .and_then(|logits| logits.i((0, 0)))
+            .and_then(|logits| logits.to_vec1())
+            .map_err(|e| model_error(format!("Final linear failed: {e}")))?;
+
+        for &token in suppressed {
+            if let Some(logit) = logits.get_mut(token) {
+                *logit = f32::NEG_INFINITY;
+            }
+        }
+
+        Ok(logits)
+    }
+
+    /// Fail if the model cannot perform `task` (English-only models cannot translate)
+    fn check_task(&self, task: Task) -> Result<()> {
+        if task == Task::Translate && self.special.translate.is_none() {
+            return Err(ScribeError::Transcription(TranscriptionError::ModelError(
+                "Model is English-only and cannot translate. Use a multilingual model".to_string(),
+            )));
+        }
+        Ok(())
+    }
+
+    fn lock_model(model: &Mutex<WhisperModel>) -> Result<MutexGuard<'_, WhisperModel>> {
+        model.lock().map_err(|_| {
+            ScribeError::Transcription(TranscriptionError::ModelError(
+                "Failed to lock model mutex".to_string(),
+            ))
+        })
+    }
+
+    /// Fail with `Cancelled` once the awaiting task has been dropped
+    fn check_cancelled(cancelled: &AtomicBool) -> Result<()> {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(ScribeError::Transcription(TranscriptionError::Cancelled));
+        }
+        Ok(())
     }
 
     /// Convert i16 audio samples to f32 normalized for Whisper
@@ -388,7 +794,13 @@ impl LocalBackend {
 
 #[async_trait]
 impl TranscriptionBackend for LocalBackend {
-    async fn transcribe(&self, audio: &[i16]) -> Result<String> {
+    async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String> {
+        Ok(self.transcribe_segments(audio, task).await?.text)
+    }
+
+    async fn transcribe_segments(&self, audio: &[i16], task: Task) -> Result<Transcription> {
+        self.check_task(task)?;
+
         // Normalize audio
         let audio_f32 = Self::normalize_audio(audio);
 
@@ -397,47 +809,101 @@ impl TranscriptionBackend for LocalBackend {
         let tokenizer = Arc::clone(&self.tokenizer);
         let mel_filters = Arc::clone(&self.mel_filters);
         let config = self.config.clone();
+        let options = self.decoding.clone();
         let device = self.device.clone();
-        let language_token = self.language_token;
-        let sot_token = self.sot_token;
-        let transcribe_token = self.transcribe_token;
-        let eot_token = self.eot_token;
-        let no_timestamps_token = self.no_timestamps_token;
+        let mut language_token = self.language_token;
+        let language_tokens = self.language_tokens.clone();
+        let special = self.special;
+        let initial_prompt_tokens = self.initial_prompt_tokens.clone();
+        let timestamp_begin = options.timestamps.then(|| special.timestamp_begin());
+
+        // Stops the blocking task below if this future is dropped (job cancelled)
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _cancel_guard = CancelOnDrop(Arc::clone(&cancelled));
 
         // Run inference in blocking task
-        let result = tokio::task::spawn_blocking(move || {
-            // Compute mel spectrogram
-            let mel = audio::pcm_to_mel(&config, &audio_f32, &mel_filters);
-            let mel_len = mel.len();
-            let num_mel_bins = config.num_mel_bins;
-            let mel_tensor =
-                Tensor::from_vec(mel, (1, num_mel_bins, mel_len / num_mel_bins), &device).map_err(
-                    |e| {
-                        ScribeError::Transcription(TranscriptionError::ModelError(format!(
-                            "Failed to create mel tensor: {e}"
-                        )))
-                    },
-                )?;
+        let (segments, detected_language) = tokio::task::spawn_blocking(move || {
+            let windows = Self::segment_boundaries(&audio_f32);
+            if windows.len() > 1 {
+                tracing::debug!(
+                    segment_count = windows.len(),
+                    "Audio exceeds 30s window, decoding in segments"
+                );
+            }
 
-            // Lock model and run inference
-            let mut model_guard = model.lock().map_err(|_| {
-                ScribeError::Transcription(TranscriptionError::ModelError(
-                    "Failed to lock model mutex".to_string(),
-                ))
-            })?;
+            // Lock model and run inference; waits for a job still decoding
+            let mut model_guard = Self::lock_model(&model)?;
 
-            Self::decode_blocking(DecodeParams {
-                model: &mut model_guard,
-                tokenizer: &tokenizer,
-                mel: &mel_tensor,
-                device: &device,
-                config: &config,
-                language_token,
-                sot_token,
-                transcribe_token,
-                eot_token,
-                no_timestamps_token,
-            })
+            let mut segments = Vec::new();
+            let mut detected_language = None;
+            let mut prompt_tokens = initial_prompt_tokens.clone();
+
+            for (index, range) in windows.into_iter().enumerate() {
+                Self::check_cancelled(&cancelled)?;
+
+                let window = Self::seconds(range.start)..Self::seconds(range.end);
+                let mel_tensor =
+                    Self::segment_mel(&config, &audio_f32[range], &mel_filters, &device)?;
+                let audio_features = model_guard.encode(&mel_tensor)?;
+
+                // Detect the language once, from the first segment
+                if language_token.is_none() && !language_tokens.is_empty() {
+                    let (language, token, probability) = Self::detect_language(
+                        &mut model_guard,
+                        &audio_features,
+                        special.sot,
+                        &language_tokens,
+                        &device,
+                    )?;
+                    tracing::info!(%language, probability, "Detected language");
+                    language_token = Some(token);
+                    detected_language = Some(language);
+                }
+
+                let result = Self::decode_blocking(DecodeParams {
+                    model: &mut model_guard,
+                    tokenizer: &tokenizer,
+                    audio_features: &audio_features,
+                    device: &device,
+                    config: &config,
+                    options: &options,
+                    prompt_tokens: &prompt_tokens,
+                    language_token,
+                    task,
+                    special,
+                    cancelled: &cancelled,
+                })?;
+
+                // Condition the next segment on the initial prompt and this one's
+                // output, unless that output was dropped or needed a high temperature
+                prompt_tokens.clone_from(&initial_prompt_tokens);
+
+                let Some(result) = result else {
+                    continue;
+                };
+
+                tracing::trace!(
+                    segment = index,
+                    text = %result.text,
+                    temperature = result.temperature,
+                    avg_logprob = result.avg_logprob,
+                    "Decoded segment"
+                );
+
+                segments.extend(Self::timed_segments(
+                    &tokenizer,
+                    &result,
+                    timestamp_begin,
+                    window,
+                )?);
+
+                if result.temperature <= PROMPT_RESET_TEMPERATURE {
+                    prompt_tokens.extend(result.tokens.iter().filter(|&&t| t < special.eot));
+                }
+            }
+            drop(model_guard);
+
+            Ok::<_, ScribeError>((segments, detected_language))
         })
         .await
         .map_err(|e| {
@@ -446,18 +912,78 @@ impl TranscriptionBackend for LocalBackend {
             )))
         })??;
 
-        Ok(Self::post_process(&result))
+        if detected_language.is_some() {
+            if let Ok(mut last) = self.detected_language.lock() {
+                last.clone_from(&detected_language);
+            }
+        }
+
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        Ok(Transcription {
+            text: Self::post_process(&texts.join(" ")),
+            segments,
+            language: detected_language,
+        })
     }
 
     fn backend_name(&self) -> &'static str {
         "local"
     }
+
+    fn detected_language(&self) -> Option<String> {
+        self.detected_language.lock().ok()?.clone()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_truncate_prompt() {
+        let tokens = [1, 2, 3, 4, 5];
+        assert_eq!(LocalBackend::truncate_prompt(&tokens, 10), &tokens);
+        assert_eq!(LocalBackend::truncate_prompt(&tokens, 5), &tokens);
+        assert_eq!(LocalBackend::truncate_prompt(&tokens, 2), &[4, 5]);
+        assert!(LocalBackend::truncate_prompt(&tokens, 0).is_empty());
+        assert!(LocalBackend::truncate_prompt(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_language_tokens() {
+        let vocab = HashMap::from([
+            ("<|no|>".to_string(), 50288),
+            ("<|en|>".to_string(), 50259),
+            ("<|haw|>".to_string(), 50352),
+            ("<|startoftranscript|>".to_string(), 50258),
+            ("<|notimestamps|>".to_string(), 50363),
+            ("<|0.00|>".to_string(), 50364),
+            ("hello".to_string(), 31373),
+        ]);
+
+        assert_eq!(
+            LocalBackend::language_tokens(vocab),
+            vec![
+                ("en".to_string(), 50259),
+                ("no".to_string(), 50288),
+                ("haw".to_string(), 50352),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancel_on_drop() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let guard = CancelOnDrop(Arc::clone(&cancelled));
+        assert!(LocalBackend::check_cancelled(&cancelled).is_ok());
+
+        drop(guard);
+        assert!(matches!(
+            LocalBackend::check_cancelled(&cancelled),
+            Err(ScribeError::Transcription(TranscriptionError::Cancelled))
+        ));
+    }
+
     #[test]
     fn test_normalize_audio() {
         let samples = vec![0i16, 16384, -16384, 32767, -32768];
@@ -480,4 +1006,45 @@ mod tests {
         assert_eq!(LocalBackend::post_process("test"), "test ");
         assert_eq!(LocalBackend::post_process(""), String::new());
     }
+
+    #[test]
+    fn test_segment_boundaries_short_audio() {
+        let samples = vec![0.0f32; 5 * m::SAMPLE_RATE];
+        let segments = LocalBackend::segment_boundaries(&samples);
+        assert_eq!(segments, vec![0..samples.len()]);
+
+        let segments = LocalBackend::segment_boundaries(&[]);
+        assert_eq!(segments, vec![0..0]);
+    }
+
+    #[test]
+    fn test_segment_boundaries_exact_window() {
+        let samples = vec![0.1f32; m::N_SAMPLES];
+        let segments = LocalBackend::segment_boundaries(&samples);
+        assert_eq!(segments, vec![0..m::N_SAMPLES]);
+    }
+
+    #[test]
+    fn test_segment_boundaries_long_audio() {
+        // 70s of "speech" with a short silent gap at 27s
+        let mut samples = vec![0.5f32; 70 * m::SAMPLE_RATE];
+        let gap = 27 * m::SAMPLE_RATE..27 * m::SAMPLE_RATE + 2 * CUT_FRAME_SAMPLES;
+        for s in &mut samples[gap.clone()] {
+            *s = 0.0;
+        }
+
+        let segments = LocalBackend::segment_boundaries(&samples);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].start, 0);
+        assert!(gap.contains(&segments[0].end));
+
+        // Segments are contiguous, cover all audio and fit the window
+        assert_eq!(segments.last().unwrap().end, samples.len());
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        for segment in &segments {
+            assert!(segment.len() <= m::N_SAMPLES);
+        }
+    }
 }
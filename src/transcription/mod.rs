@@ -1,54 +1,181 @@
 pub mod api;
+pub mod fallback;
 pub mod local;
+pub mod providers;
+
+mod decoding;
 
 use crate::config::schema::TranscriptionConfig;
 use crate::error::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 pub use api::OpenAIBackend;
+pub use fallback::FallbackBackend;
 pub use local::LocalBackend;
 
+/// Sample rate (Hz) that all transcription backends expect
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// What a backend does with the speech it recognizes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Task {
+    /// Write down speech in the language it was spoken in
+    #[default]
+    Transcribe,
+    /// Translate speech into English
+    Translate,
+}
+
+impl Task {
+    /// Task configured in `TranscriptionConfig::task`
+    #[must_use]
+    pub fn from_config(config: &TranscriptionConfig) -> Self {
+        config.task.parse().unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for Task {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "transcribe" => Ok(Self::Transcribe),
+            "translate" => Ok(Self::Translate),
+            _ => Err(format!(
+                "Unknown task: '{s}'. Must be 'transcribe' or 'translate'"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transcribe => write!(f, "transcribe"),
+            Self::Translate => write!(f, "translate"),
+        }
+    }
+}
+
+/// Transcribed text together with its timed segments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcription {
+    /// Full text with trailing space for continuous typing
+    pub text: String,
+    /// Timed segments in order; empty if the backend does not provide them
+    pub segments: Vec<Segment>,
+    /// Language detected for this audio, if the backend auto-detected it
+    pub language: Option<String>,
+}
+
+/// A span of transcribed speech
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Start time in seconds from the beginning of the audio
+    pub start: f64,
+    /// End time in seconds from the beginning of the audio
+    pub end: f64,
+    pub text: String,
+    /// Average log-probability of the segment's tokens, a confidence measure
+    /// (closer to 0 is better)
+    pub avg_logprob: f32,
+}
+
 /// Unified interface for transcription backends
 #[async_trait]
-pub trait TranscriptionBackend: Send + Sync {
+pub trait TranscriptionBackend: Send + Sync + std::fmt::Debug {
     /// Transcribe audio samples to text
     ///
     /// # Arguments
     /// * `audio` - i16 audio samples at 16kHz, mono
+    /// * `task` - Transcribe in the spoken language or translate into English
     ///
     /// # Returns
     /// Transcribed text with trailing space for continuous typing
-    async fn transcribe(&self, audio: &[i16]) -> Result<String>;
+    async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String>;
+
+    /// Transcribe audio samples into text and timed segments
+    ///
+    /// Backends without segment information return the text alone.
+    async fn transcribe_segments(&self, audio: &[i16], task: Task) -> Result<Transcription> {
+        Ok(Transcription {
+            text: self.transcribe(audio, task).await?,
+            ..Transcription::default()
+        })
+    }
 
     /// Get backend name for logging/debugging
     fn backend_name(&self) -> &str;
+
+    /// Language detected in the most recent transcription, if the backend
+    /// auto-detects it
+    fn detected_language(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Backend enum wrapper for dynamic dispatch
 #[derive(Debug)]
 pub enum Backend {
-    Local(LocalBackend),
-    OpenAI(OpenAIBackend),
+    Local(Box<LocalBackend>),
+    /// Backend of a remote provider, see [`providers`]
+    Remote(Box<dyn TranscriptionBackend>),
+    /// Remote backend with a local fallback
+    Fallback(Box<FallbackBackend>),
 }
 
 impl Backend {
     /// Create backend from config
     pub async fn from_config(config: &TranscriptionConfig) -> Result<Self> {
-        match config.backend.as_str() {
-            "local" => Ok(Self::Local(LocalBackend::new(config).await?)),
-            "openai" => Ok(Self::OpenAI(OpenAIBackend::new(config)?)),
-            _ => Err(crate::error::ScribeError::Config(format!(
-                "Unknown backend: {}. Must be 'local' or 'openai'",
-                config.backend
-            ))),
+        if config.backend == "local" {
+            return Ok(Self::Local(Box::new(LocalBackend::new(config).await?)));
+        }
+
+        let provider = providers::find(&config.backend).ok_or_else(|| {
+            crate::error::ScribeError::Config(format!(
+                "Unknown backend: {}. Must be 'local' or one of: {:?}",
+                config.backend,
+                providers::names()
+            ))
+        })?;
+        let remote = provider.create(config)?;
+
+        if config.fallback_backend.as_deref() == Some("local") {
+            Ok(Self::Fallback(Box::new(FallbackBackend::new(
+                remote, config,
+            ))))
+        } else {
+            Ok(Self::Remote(remote))
         }
     }
 
     /// Transcribe audio using the configured backend
-    pub async fn transcribe(&self, audio: &[i16]) -> Result<String> {
+    pub async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String> {
+        match self {
+            Self::Local(b) => b.transcribe(audio, task).await,
+            Self::Remote(b) => b.transcribe(audio, task).await,
+            Self::Fallback(b) => b.transcribe(audio, task).await,
+        }
+    }
+
+    /// Transcribe audio into text and timed segments using the configured backend
+    pub async fn transcribe_segments(&self, audio: &[i16], task: Task) -> Result<Transcription> {
+        match self {
+            Self::Local(b) => b.transcribe_segments(audio, task).await,
+            Self::Remote(b) => b.transcribe_segments(audio, task).await,
+            Self::Fallback(b) => b.transcribe_segments(audio, task).await,
+        }
+    }
+
+    /// Get language detected in the most recent transcription
+    #[must_use]
+    pub fn detected_language(&self) -> Option<String> {
         match self {
-            Self::Local(b) => b.transcribe(audio).await,
-            Self::OpenAI(b) => b.transcribe(audio).await,
+            Self::Local(b) => b.detected_language(),
+            Self::Remote(b) => b.detected_language(),
+            Self::Fallback(b) => b.detected_language(),
         }
     }
 
@@ -57,7 +184,8 @@ impl Backend {
     pub fn backend_name(&self) -> &str {
         match self {
             Self::Local(b) => b.backend_name(),
-            Self::OpenAI(b) => b.backend_name(),
+            Self::Remote(b) => b.backend_name(),
+            Self::Fallback(b) => b.backend_name(),
         }
     }
 }
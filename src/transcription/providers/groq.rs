@@ -0,0 +1,54 @@
+use crate::config::schema::TranscriptionConfig;
+use crate::error::Result;
+use crate::transcription::api::Endpoint;
+use crate::transcription::providers::{self, Provider};
+use crate::transcription::{OpenAIBackend, TranscriptionBackend};
+use serde::Deserialize;
+
+/// Groq's hosted Whisper, an OpenAI-compatible API
+pub struct GroqProvider;
+
+/// `[transcription.providers.groq]`
+#[derive(Deserialize, Debug)]
+struct GroqSettings {
+    #[serde(default = "default_api_key_env")]
+    api_key_env: String,
+    /// `whisper-large-v3-turbo`, `whisper-large-v3` (which can also translate)
+    /// or `distil-whisper-large-v3-en`
+    #[serde(default = "default_model")]
+    model: String,
+    #[serde(default = "default_base_url")]
+    base_url: String,
+}
+
+fn default_api_key_env() -> String {
+    "GROQ_API_KEY".to_string()
+}
+fn default_model() -> String {
+    "whisper-large-v3-turbo".to_string()
+}
+fn default_base_url() -> String {
+    "https://api.groq.com/openai/v1".to_string()
+}
+
+impl Provider for GroqProvider {
+    fn name(&self) -> &'static str {
+        "groq"
+    }
+
+    fn validate(&self, config: &TranscriptionConfig) -> Result<()> {
+        providers::settings::<GroqSettings>(config, self.name()).map(|_| ())
+    }
+
+    fn create(&self, config: &TranscriptionConfig) -> Result<Box<dyn TranscriptionBackend>> {
+        let settings: GroqSettings = providers::settings(config, self.name())?;
+        let endpoint = Endpoint {
+            name: self.name(),
+            base_url: settings.base_url,
+            api_key_env: settings.api_key_env,
+            model: settings.model,
+            require_key: true,
+        };
+        Ok(Box::new(OpenAIBackend::with_endpoint(config, endpoint)?))
+    }
+}
@@ -0,0 +1,284 @@
+use crate::config::schema::TranscriptionConfig;
+use crate::error::{Result, ScribeError, TranscriptionError};
+use crate::transcription::api::{self, RetryPolicy};
+use crate::transcription::providers::{self, Provider};
+use crate::transcription::{Task, TranscriptionBackend, SAMPLE_RATE};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Any service that takes a multipart audio upload and answers with JSON
+pub struct HttpProvider;
+
+/// `[transcription.providers.http]`
+#[derive(Deserialize, Debug, Clone)]
+struct HttpSettings {
+    /// Endpoint receiving the upload
+    url: String,
+    /// Endpoint that translates into English, if the service has one
+    translate_url: Option<String>,
+    /// Environment variable containing the API key; requests are unauthenticated without it
+    api_key_env: Option<String>,
+    #[serde(default = "default_auth_header")]
+    auth_header: String,
+    /// Prefix before the key, e.g. "Bearer" or "Token"; empty sends the key alone
+    #[serde(default = "default_auth_scheme")]
+    auth_scheme: String,
+    /// Form field carrying the WAV file
+    #[serde(default = "default_file_field")]
+    file_field: String,
+    /// Form field carrying `transcription.language`, if the service takes one
+    language_field: Option<String>,
+    /// Extra form fields sent with every request
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+    /// Dot-separated path to the text in the JSON response; numbers index arrays
+    #[serde(default = "default_text_path")]
+    text_path: String,
+}
+
+fn default_auth_header() -> String {
+    "Authorization".to_string()
+}
+fn default_auth_scheme() -> String {
+    "Bearer".to_string()
+}
+fn default_file_field() -> String {
+    "file".to_string()
+}
+fn default_text_path() -> String {
+    "text".to_string()
+}
+
+impl Provider for HttpProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn validate(&self, config: &TranscriptionConfig) -> Result<()> {
+        let settings: HttpSettings = providers::settings(config, self.name())?;
+
+        for url in std::iter::once(&settings.url).chain(&settings.translate_url) {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(ScribeError::Config(format!(
+                    "Invalid URL in [transcription.providers.http]: '{url}'. Must start with http:// or https://"
+                )));
+            }
+        }
+        if reqwest::header::HeaderName::from_bytes(settings.auth_header.as_bytes()).is_err() {
+            return Err(ScribeError::Config(format!(
+                "Invalid auth_header in [transcription.providers.http]: '{}'. Must be a valid HTTP header name",
+                settings.auth_header
+            )));
+        }
+        if settings.text_path.is_empty() {
+            return Err(ScribeError::Config(
+                "text_path in [transcription.providers.http] must not be empty".to_string(),
+            ));
+        }
+        if config.task == "translate" && settings.translate_url.is_none() {
+            return Err(ScribeError::Config(
+                "task = \"translate\" needs translate_url in [transcription.providers.http]"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn create(&self, config: &TranscriptionConfig) -> Result<Box<dyn TranscriptionBackend>> {
+        Ok(Box::new(HttpBackend::new(config)?))
+    }
+}
+
+/// Generic multipart-upload transcription backend
+pub struct HttpBackend {
+    client: reqwest::Client,
+    settings: HttpSettings,
+    api_key: Option<String>,
+    language: String,
+    timeout: Duration,
+    retry: RetryPolicy,
+}
+
+impl std::fmt::Debug for HttpBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpBackend")
+            .field("client", &"Client { ... }")
+            .field("settings", &self.settings)
+            .field("api_key", &self.api_key.as_ref().map(|_| "***"))
+            .field("language", &self.language)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+impl HttpBackend {
+    /// Create backend from `[transcription.providers.http]`
+    pub fn new(config: &TranscriptionConfig) -> Result<Self> {
+        let settings: HttpSettings = providers::settings(config, "http")?;
+
+        // A configured key variable must be set; no variable means no auth
+        let api_key = match &settings.api_key_env {
+            Some(name) => Some(api::api_key(name).ok_or(ScribeError::Transcription(
+                TranscriptionError::InvalidApiKey,
+            ))?),
+            None => None,
+        };
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            settings,
+            api_key,
+            language: config.language.clone(),
+            timeout: api::request_timeout(config),
+            retry: RetryPolicy::from_config(config),
+        })
+    }
+
+    /// Value of the auth header for `api_key`
+    fn auth_value(&self, api_key: &str) -> String {
+        if self.settings.auth_scheme.is_empty() {
+            api_key.to_string()
+        } else {
+            format!("{} {api_key}", self.settings.auth_scheme)
+        }
+    }
+
+    /// Send one transcription request
+    async fn request(&self, wav_bytes: &[u8], task: Task) -> Result<String> {
+        let url = match task {
+            Task::Transcribe => &self.settings.url,
+            Task::Translate => self.settings.translate_url.as_ref().ok_or_else(|| {
+                ScribeError::Config(
+                    "Provider 'http' cannot translate without translate_url".to_string(),
+                )
+            })?,
+        };
+
+        let mut form = reqwest::multipart::Form::new().part(
+            self.settings.file_field.clone(),
+            api::wav_part(wav_bytes.to_vec())?,
+        );
+        for (name, value) in &self.settings.fields {
+            form = form.text(name.clone(), value.clone());
+        }
+        if let Some(field) = &self.settings.language_field {
+            if task == Task::Transcribe && !self.language.is_empty() {
+                form = form.text(field.clone(), self.language.clone());
+            }
+        }
+
+        let mut request = self.client.post(url);
+        if let Some(api_key) = &self.api_key {
+            request = request.header(&self.settings.auth_header, self.auth_value(api_key));
+        }
+        let json = api::send(request.multipart(form).timeout(self.timeout)).await?;
+
+        let text = json_path(&json, &self.settings.text_path)
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                ScribeError::Transcription(TranscriptionError::ApiError(format!(
+                    "Missing '{}' in API response",
+                    self.settings.text_path
+                )))
+            })?;
+
+        Ok(api::post_process(text))
+    }
+}
+
+/// Follow a dot-separated `path` into `value`; numeric segments index arrays
+fn json_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(value, |value, key| match value {
+        serde_json::Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+#[async_trait]
+impl TranscriptionBackend for HttpBackend {
+    async fn transcribe(&self, audio: &[i16], task: Task) -> Result<String> {
+        let wav_bytes = api::audio_to_wav(audio, SAMPLE_RATE)?;
+        self.retry.run(|| self.request(&wav_bytes, task)).await
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "http"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde_json::json;
+
+    fn config(table: &str) -> TranscriptionConfig {
+        let mut config = Config::default().transcription;
+        config.backend = "http".to_string();
+        config
+            .providers
+            .insert("http".to_string(), toml::from_str(table).unwrap());
+        config
+    }
+
+    #[test]
+    fn test_json_path() {
+        let response = json!({
+            "text": "plain",
+            "results": {"channels": [{"alternatives": [{"transcript": "nested"}]}]}
+        });
+
+        assert_eq!(json_path(&response, "text"), Some(&json!("plain")));
+        assert_eq!(
+            json_path(&response, "results.channels.0.alternatives.0.transcript"),
+            Some(&json!("nested"))
+        );
+        assert_eq!(json_path(&response, "results.channels.1"), None);
+        assert_eq!(json_path(&response, "results.channels.first"), None);
+        assert_eq!(json_path(&response, "missing"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let provider = HttpProvider;
+        assert!(provider
+            .validate(&config("url = \"https://stt.example.com/v1\""))
+            .is_ok());
+        assert!(provider.validate(&config("text_path = \"text\"")).is_err());
+        assert!(provider
+            .validate(&config("url = \"stt.example.com\""))
+            .is_err());
+
+        let error = provider
+            .validate(&config(
+                "url = \"https://stt.example.com/v1\"\nauth_header = \"X Api Key\"",
+            ))
+            .unwrap_err();
+        assert!(error.to_string().contains("[transcription.providers.http]"));
+        assert!(provider
+            .validate(&config(
+                "url = \"https://stt.example.com/v1\"\nauth_header = \"X-Api-Key\"",
+            ))
+            .is_ok());
+
+        let mut translate = config("url = \"https://stt.example.com/v1\"");
+        translate.task = "translate".to_string();
+        assert!(provider.validate(&translate).is_err());
+    }
+
+    #[test]
+    fn test_auth_value() {
+        let backend = HttpBackend::new(&config("url = \"https://stt.example.com\"")).unwrap();
+        assert_eq!(backend.auth_value("key"), "Bearer key");
+
+        let backend = HttpBackend::new(&config(
+            "url = \"https://stt.example.com\"\nauth_header = \"X-Api-Key\"\nauth_scheme = \"\"",
+        ))
+        .unwrap();
+        assert_eq!(backend.auth_value("key"), "key");
+    }
+}
@@ -0,0 +1,112 @@
+//! Remote transcription providers
+//!
+//! Each provider is selected by name with `transcription.backend` and reads its
+//! own settings from `[transcription.providers.<name>]`. Providers other than
+//! `OpenAI` are compiled in through cargo features.
+
+#[cfg(feature = "provider-groq")]
+mod groq;
+#[cfg(feature = "provider-http")]
+mod http;
+mod openai;
+
+use crate::config::schema::TranscriptionConfig;
+use crate::error::{Result, ScribeError};
+use crate::transcription::TranscriptionBackend;
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "provider-groq")]
+pub use groq::GroqProvider;
+#[cfg(feature = "provider-http")]
+pub use http::{HttpBackend, HttpProvider};
+pub use openai::OpenAIProvider;
+
+/// A remote transcription service
+pub trait Provider: Sync {
+    /// Value of `transcription.backend` that selects this provider
+    fn name(&self) -> &'static str;
+
+    /// Check the provider's settings, without contacting the service
+    fn validate(&self, config: &TranscriptionConfig) -> Result<()>;
+
+    /// Create a backend for the provider
+    fn create(&self, config: &TranscriptionConfig) -> Result<Box<dyn TranscriptionBackend>>;
+}
+
+/// Providers compiled into this build
+static PROVIDERS: &[&dyn Provider] = &[
+    &OpenAIProvider,
+    #[cfg(feature = "provider-groq")]
+    &GroqProvider,
+    #[cfg(feature = "provider-http")]
+    &HttpProvider,
+];
+
+/// Look up a provider by backend name
+#[must_use]
+pub fn find(name: &str) -> Option<&'static dyn Provider> {
+    PROVIDERS
+        .iter()
+        .copied()
+        .find(|provider| provider.name() == name)
+}
+
+/// Names of all available providers
+#[must_use]
+pub fn names() -> Vec<&'static str> {
+    PROVIDERS.iter().map(|provider| provider.name()).collect()
+}
+
+/// Parse the `[transcription.providers.<name>]` table
+///
+/// A missing table parses as empty, so settings with defaults may be omitted.
+pub fn settings<T: DeserializeOwned>(config: &TranscriptionConfig, name: &str) -> Result<T> {
+    config
+        .providers
+        .get(name)
+        .cloned()
+        .unwrap_or_default()
+        .try_into()
+        .map_err(|e| ScribeError::Config(format!("Invalid [transcription.providers.{name}]: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug)]
+    struct TestSettings {
+        url: String,
+        #[serde(default)]
+        retries: u32,
+    }
+
+    #[test]
+    fn test_find_provider() {
+        assert_eq!(find("openai").map(Provider::name), Some("openai"));
+        assert!(find("local").is_none());
+        assert!(find("nope").is_none());
+        assert!(names().contains(&"openai"));
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut config = Config::default().transcription;
+        config.providers.insert(
+            "test".to_string(),
+            toml::from_str("url = \"http://localhost\"").unwrap(),
+        );
+
+        let parsed: TestSettings = settings(&config, "test").unwrap();
+        assert_eq!(parsed.url, "http://localhost");
+        assert_eq!(parsed.retries, 0);
+
+        // Required settings are reported with the table name
+        let error = settings::<TestSettings>(&config, "missing").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("[transcription.providers.missing]"));
+    }
+}
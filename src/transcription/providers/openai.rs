@@ -0,0 +1,22 @@
+use crate::config::schema::TranscriptionConfig;
+use crate::error::Result;
+use crate::transcription::providers::Provider;
+use crate::transcription::{OpenAIBackend, TranscriptionBackend};
+
+/// `OpenAI` and OpenAI-compatible servers, configured with the `api_*` settings
+pub struct OpenAIProvider;
+
+impl Provider for OpenAIProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn validate(&self, _config: &TranscriptionConfig) -> Result<()> {
+        // The shared api_* settings are checked by the config itself
+        Ok(())
+    }
+
+    fn create(&self, config: &TranscriptionConfig) -> Result<Box<dyn TranscriptionBackend>> {
+        Ok(Box::new(OpenAIBackend::new(config)?))
+    }
+}
@@ -1,4 +1,6 @@
-use scribe::ipc::{client::IpcClient, server::IpcServer, AppStatus, Command, Response};
+use scribe::ipc::{
+    client::IpcClient, server::IpcServer, AppStatus, Command, Response, StatusReport,
+};
 use std::path::PathBuf;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::{sleep, Duration};
@@ -17,7 +19,7 @@ async fn test_ipc_communication() {
 
     // Set up channels
     let (command_tx, mut command_rx) = mpsc::channel::<Command>(32);
-    let (status_tx, status_rx) = mpsc::channel::<AppStatus>(32);
+    let (status_tx, status_rx) = mpsc::channel::<StatusReport>(32);
     let (ready_tx, ready_rx) = oneshot::channel();
 
     // Start server in background
@@ -31,7 +33,7 @@ async fn test_ipc_communication() {
 
     // Send initial status
     status_tx
-        .send(AppStatus::Idle)
+        .send(StatusReport::new(AppStatus::Idle))
         .await
         .expect("Failed to send initial status");
 
@@ -75,7 +77,10 @@ async fn test_ipc_communication() {
         .expect("Failed to send status command");
 
     match response {
-        Response::Status(AppStatus::Idle) => {}
+        Response::Status(StatusReport {
+            state: AppStatus::Idle,
+            language: None,
+        }) => {}
         _ => panic!("Expected Status(Idle), got {response:?}"),
     }
 
@@ -90,7 +95,7 @@ async fn test_multiple_clients() {
 
     // Set up channels
     let (command_tx, mut command_rx) = mpsc::channel::<Command>(32);
-    let (status_tx, status_rx) = mpsc::channel::<AppStatus>(32);
+    let (status_tx, status_rx) = mpsc::channel::<StatusReport>(32);
     let (ready_tx, ready_rx) = oneshot::channel();
 
     // Start server
@@ -103,7 +108,7 @@ async fn test_multiple_clients() {
     });
 
     status_tx
-        .send(AppStatus::Idle)
+        .send(StatusReport::new(AppStatus::Idle))
         .await
         .expect("Failed to send initial status");
 
@@ -157,7 +162,7 @@ async fn test_cancel_command() {
 
     // Set up channels
     let (command_tx, mut command_rx) = mpsc::channel::<Command>(32);
-    let (status_tx, status_rx) = mpsc::channel::<AppStatus>(32);
+    let (status_tx, status_rx) = mpsc::channel::<StatusReport>(32);
     let (ready_tx, ready_rx) = oneshot::channel();
 
     // Start server
@@ -170,7 +175,7 @@ async fn test_cancel_command() {
     });
 
     status_tx
-        .send(AppStatus::Idle)
+        .send(StatusReport::new(AppStatus::Idle))
         .await
         .expect("Failed to send initial status");
 